@@ -0,0 +1,172 @@
+use crate::solution::Part;
+use std::{collections::BTreeMap, fmt::Display};
+
+// Accepted answers, stored as a small subset of TOML:
+//
+// [[answer]]
+// day = 5
+// part = 2
+// expected = "46"
+#[derive(Debug, Default, PartialEq)]
+pub struct Answers {
+    entries: BTreeMap<(u32, Part), String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String, actual: String },
+    Missing,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "PASS"),
+            Verdict::Fail { expected, actual } => {
+                write!(
+                    f,
+                    "FAIL\n  - expected: {}\n  + actual:   {}",
+                    expected, actual
+                )
+            }
+            Verdict::Missing => write!(f, "MISSING"),
+        }
+    }
+}
+
+impl Answers {
+    pub fn parse(contents: &str) -> Result<Self, String> {
+        let mut entries = BTreeMap::new();
+        let mut current: Option<(Option<u32>, Option<Part>, Option<String>)> = None;
+
+        let mut finish = |entry: Option<(Option<u32>, Option<Part>, Option<String>)>,
+                          line: usize|
+         -> Result<(), String> {
+            match entry {
+                None => Ok(()),
+                Some((Some(day), Some(part), Some(expected))) => {
+                    entries.insert((day, part), expected);
+                    Ok(())
+                }
+                Some(_) => Err(format!(
+                    "line {}: answer needs a day, part and expected value",
+                    line
+                )),
+            }
+        };
+
+        for (index, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if line == "[[answer]]" {
+                finish(current.take(), index)?;
+                current = Some((None, None, None));
+                continue;
+            }
+
+            let error = || format!("line {}: unexpected `{}`", index + 1, line);
+            let (key, value) = line.split_once('=').ok_or_else(error)?;
+            let entry = current.as_mut().ok_or_else(error)?;
+            let value = value.trim();
+
+            match key.trim() {
+                "day" => entry.0 = Some(value.parse().map_err(|_| error())?),
+                "part" => entry.1 = Some(Part::parse(value).ok_or_else(error)?),
+                "expected" => {
+                    let expected = value
+                        .strip_prefix('"')
+                        .and_then(|value| value.strip_suffix('"'))
+                        .ok_or_else(error)?;
+
+                    entry.2 = Some(expected.replace("\\\"", "\"").replace("\\\\", "\\"));
+                }
+                _ => return Err(error()),
+            }
+        }
+
+        finish(current, contents.lines().count())?;
+
+        Ok(Self { entries })
+    }
+
+    pub fn get(&self, day: u32, part: Part) -> Option<&str> {
+        self.entries
+            .get(&(day, part))
+            .map(|expected| expected.as_str())
+    }
+
+    pub fn record(&mut self, day: u32, part: Part, answer: &str) {
+        self.entries.insert((day, part), answer.to_string());
+    }
+
+    pub fn verify(&self, day: u32, part: Part, actual: &str) -> Verdict {
+        match self.get(day, part) {
+            None => Verdict::Missing,
+            Some(expected) if expected == actual => Verdict::Pass,
+            Some(expected) => Verdict::Fail {
+                expected: expected.to_string(),
+                actual: actual.to_string(),
+            },
+        }
+    }
+}
+
+impl Display for Answers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (index, ((day, part), expected)) in self.entries.iter().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+
+            let expected = expected.replace('\\', "\\\\").replace('"', "\\\"");
+            writeln!(f, "[[answer]]")?;
+            writeln!(f, "day = {}", day)?;
+            writeln!(f, "part = {}", part)?;
+            writeln!(f, "expected = \"{}\"", expected)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ANSWERS: &str = "[[answer]]\nday = 5\npart = 2\nexpected = \"46\"\n\n[[answer]]\nday = 25\npart = 2\nexpected = \"Merry Christmas!\"\n";
+
+    #[test]
+    fn test_round_trip() {
+        let answers = Answers::parse(ANSWERS).unwrap();
+
+        assert_eq!(answers.get(5, Part::Two), Some("46"));
+        assert_eq!(answers.get(5, Part::One), None);
+        assert_eq!(answers.to_string(), ANSWERS);
+    }
+
+    #[test]
+    fn test_verify() {
+        let mut answers = Answers::parse(ANSWERS).unwrap();
+        answers.record(1, Part::One, "142");
+
+        assert_eq!(answers.verify(1, Part::One, "142"), Verdict::Pass);
+        assert_eq!(answers.verify(1, Part::Two, "281"), Verdict::Missing);
+        assert_eq!(
+            answers.verify(5, Part::Two, "47"),
+            Verdict::Fail {
+                expected: "46".to_string(),
+                actual: "47".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_incomplete_answer() {
+        assert!(Answers::parse("[[answer]]\nday = 5\nexpected = \"46\"").is_err());
+        assert!(Answers::parse("day = 5").is_err());
+    }
+}
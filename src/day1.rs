@@ -1,133 +1,268 @@
-pub mod part1 {
-    use std::fs;
-    pub fn run() {
-        let contents = fs::read_to_string("inputs/day1.txt").expect("File not found");
+use crate::{
+    error::{number, split_once, LoadError, ParseError},
+    solution::Solution,
+};
+use std::{collections::BTreeMap, fs, path::Path};
 
-        let mut result = 0;
+const NUMERALS: [(&str, u32); 10] = [
+    ("0", 0),
+    ("1", 1),
+    ("2", 2),
+    ("3", 3),
+    ("4", 4),
+    ("5", 5),
+    ("6", 6),
+    ("7", 7),
+    ("8", 8),
+    ("9", 9),
+];
 
-        for line in contents.split('\n') {
-            let mut first_digit = -1;
-            let mut last_digit = -1;
+const ENGLISH_WORDS: [(&str, u32); 9] = [
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+];
 
-            for letter in line.split("") {
-                match letter.parse::<i32>() {
-                    Ok(letter_numeric) => {
-                        if first_digit < 0 {
-                            first_digit = letter_numeric;
-                        }
+// The words that spell digits in some language
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Vocabulary {
+    pub words: Vec<(String, u32)>,
+}
 
-                        last_digit = letter_numeric;
-                    }
-                    Err(_error) => {}
-                }
-            }
+impl Vocabulary {
+    pub fn english() -> Self {
+        Self {
+            words: ENGLISH_WORDS
+                .iter()
+                .map(|&(word, digit)| (word.to_string(), digit))
+                .collect(),
+        }
+    }
 
-            if first_digit >= 0 && last_digit >= 0 {
-                match format!("{}{}", first_digit, last_digit).parse::<i32>() {
-                    Ok(numeric) => {
-                        result += numeric;
-                    }
-                    Err(_error) => {}
-                }
+    // One `word = digit` entry per line, e.g. `eins = 1`. Lines starting with `#` are comments.
+    pub fn parse(contents: &str) -> Result<Self, ParseError> {
+        let entry = |line: &str| {
+            let (word, digit) = split_once(line, "=")?;
+            let (word, digit) = (word.trim(), number::<u32>(digit)?);
+
+            if word.is_empty() {
+                return Err(ParseError::new(line, "expected a word before `=` in"));
+            }
+            if digit > 9 {
+                return Err(ParseError::new(line, "expected a digit from 0 to 9 in"));
             }
-        }
 
-        println!("Day 1 Part 1: {}", result);
+            Ok((word.to_string(), digit))
+        };
+
+        let words = contents
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+            .map(|(index, line)| entry(line).map_err(|error| error.on_line(index, line)))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { words })
+    }
+
+    pub fn load(path: &Path) -> Result<Self, LoadError> {
+        let contents = fs::read_to_string(path).map_err(|error| LoadError::Read {
+            path: path.display().to_string(),
+            error,
+        })?;
+
+        Vocabulary::parse(&contents).map_err(|error| LoadError::Parse {
+            path: Some(path.display().to_string()),
+            error,
+        })
     }
 }
 
-pub mod part2 {
-    use std::fs;
+// A digit found in a line and the byte offset where its token starts
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub struct DigitMatch {
+    pub digit: u32,
+    pub position: usize,
+}
+
+#[derive(Debug, Default)]
+struct TrieNode {
+    children: BTreeMap<char, usize>,
+    digit: Option<u32>,
+}
+
+// A trie over the tokens that spell a digit. The scan walks the trie from every position, so
+// tokens sharing letters such as `twone` or `eightwo` each report their own digit.
+// With `ignore_case` tokens and lines are compared in lowercase.
+#[derive(Debug)]
+pub struct DigitScanner {
+    nodes: Vec<TrieNode>,
+    ignore_case: bool,
+}
 
-    fn line_to_numerics(line: &str) -> String {
-        let mut result = "".to_string();
+impl DigitScanner {
+    pub fn new<'a>(tokens: impl IntoIterator<Item = (&'a str, u32)>, ignore_case: bool) -> Self {
+        let mut nodes = vec![TrieNode::default()];
 
-        for letter in line.split("") {
-            result.push_str(letter);
-            result = result
-                .replace("one", "1")
-                .replace("two", "2")
-                .replace("three", "3")
-                .replace("four", "4")
-                .replace("five", "5")
-                .replace("six", "6")
-                .replace("seven", "7")
-                .replace("eight", "8")
-                .replace("nine", "9");
+        for (token, digit) in tokens {
+            let token = if ignore_case {
+                token.to_lowercase()
+            } else {
+                token.to_string()
+            };
+
+            let mut node = 0;
+            for c in token.chars() {
+                node = match nodes[node].children.get(&c) {
+                    Some(&child) => child,
+                    None => {
+                        nodes.push(TrieNode::default());
+                        let child = nodes.len() - 1;
+                        nodes[node].children.insert(c, child);
+                        child
+                    }
+                };
+            }
+
+            nodes[node].digit = Some(digit);
         }
 
-        result
+        Self { nodes, ignore_case }
     }
 
-    fn line_to_numerics_reverse(line: &str) -> String {
-        let mut result = "".to_string();
+    // Only the digits 0 to 9 themselves
+    pub fn numerals() -> Self {
+        Self::new(NUMERALS, false)
+    }
 
-        for letter in line.chars().rev() {
-            result = format!("{}{}", letter, result);
-            result = result
-                .replace("one", "1")
-                .replace("two", "2")
-                .replace("three", "3")
-                .replace("four", "4")
-                .replace("five", "5")
-                .replace("six", "6")
-                .replace("seven", "7")
-                .replace("eight", "8")
-                .replace("nine", "9");
-        }
+    // Digits and the English words one to nine
+    pub fn english() -> Self {
+        Self::with_vocabulary(&Vocabulary::english(), false)
+    }
+
+    // Digits and the words of `vocabulary`
+    pub fn with_vocabulary(vocabulary: &Vocabulary, ignore_case: bool) -> Self {
+        let words = vocabulary
+            .words
+            .iter()
+            .map(|(word, digit)| (word.as_str(), *digit));
 
-        result
+        Self::new(NUMERALS.into_iter().chain(words), ignore_case)
     }
 
-    fn line_to_digit(line: &str) -> i32 {
-        let mut first_digit = -1;
-        let mut last_digit = -1;
+    // Every digit in the line, left to right
+    pub fn scan(&self, line: &str) -> Vec<DigitMatch> {
+        line.char_indices()
+            .filter_map(|(position, _)| {
+                self.token_at(&line[position..])
+                    .map(|digit| DigitMatch { digit, position })
+            })
+            .collect()
+    }
 
-        let line_numerics = line_to_numerics(line);
-        let line_numerics_reverse = line_to_numerics_reverse(line);
+    // The digit spelled by a token at the very start of `text`. When one token is a prefix of
+    // another the shorter one wins.
+    fn token_at(&self, text: &str) -> Option<u32> {
+        let mut node = 0;
 
-        for letter in line_numerics.split("") {
-            match letter.parse::<i32>() {
-                Ok(letter_numeric) => {
-                    if first_digit < 0 {
-                        first_digit = letter_numeric;
-                    }
+        for c in text.chars() {
+            if self.ignore_case {
+                for lower in c.to_lowercase() {
+                    node = self.child(node, lower)?;
                 }
-                Err(_error) => {}
+            } else {
+                node = self.child(node, c)?;
             }
-        }
 
-        for letter in line_numerics_reverse.split("") {
-            match letter.parse::<i32>() {
-                Ok(letter_numeric) => {
-                    last_digit = letter_numeric;
-                }
-                Err(_error) => {}
+            if let Some(digit) = self.nodes[node].digit {
+                return Some(digit);
             }
         }
 
-        if first_digit >= 0 && last_digit >= 0 {
-            match format!("{}{}", first_digit, last_digit).parse::<i32>() {
-                Ok(numeric) => {
-                    return numeric;
-                }
-                Err(_error) => {}
-            }
-        }
+        None
+    }
 
-        0
+    fn child(&self, node: usize, c: char) -> Option<usize> {
+        self.nodes[node].children.get(&c).copied()
     }
+}
 
-    pub fn run() {
-        let contents = fs::read_to_string("inputs/day1.txt").expect("File not found");
+// The first and last digit of a line read as a two-digit number, 0 for a line without digits
+pub fn calibration_value(scanner: &DigitScanner, line: &str) -> i32 {
+    let matches = scanner.scan(line);
 
-        let mut result = 0;
+    match (matches.first(), matches.last()) {
+        (Some(first), Some(last)) => (first.digit * 10 + last.digit) as i32,
+        _ => 0,
+    }
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = Vec<String>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(contents: &str) -> Result<Self::Input, ParseError> {
+        Ok(contents.lines().map(|line| line.to_string()).collect())
+    }
 
-        for line in contents.split('\n') {
-            result += line_to_digit(line)
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1::calibration_sum(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2::calibration_sum(input)
+    }
+}
+
+pub mod part1 {
+    use super::*;
+
+    pub fn calibration_sum(lines: &[String]) -> i32 {
+        let scanner = DigitScanner::numerals();
+
+        lines
+            .iter()
+            .map(|line| calibration_value(&scanner, line))
+            .sum()
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_day1_part1() {
+            assert_eq!(
+                Day1::part1(&Day1::parse("1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet").unwrap()),
+                142
+            );
         }
+    }
+}
 
-        println!("Day 1 Part 2: {}", result);
+pub mod part2 {
+    use super::*;
+
+    pub fn line_to_digit(line: &str) -> i32 {
+        calibration_value(&DigitScanner::english(), line)
+    }
+
+    pub fn calibration_sum(lines: &[String]) -> i32 {
+        let scanner = DigitScanner::english();
+
+        lines
+            .iter()
+            .map(|line| calibration_value(&scanner, line))
+            .sum()
     }
 
     #[cfg(test)]
@@ -166,5 +301,70 @@ pub mod part2 {
         fn test_line_to_digit8() {
             assert_eq!(line_to_digit("qkdoneighttwo1one3"), 13);
         }
+        #[test]
+        fn test_line_to_digit_overlaps() {
+            assert_eq!(line_to_digit("twone"), 21);
+            assert_eq!(line_to_digit("eightwo"), 82);
+            assert_eq!(line_to_digit("5oneight"), 58);
+            assert_eq!(line_to_digit("nothing"), 0);
+        }
+
+        #[test]
+        fn test_scan_positions() {
+            let digits: Vec<(u32, usize)> = DigitScanner::english()
+                .scan("xtwone3four")
+                .iter()
+                .map(|found| (found.digit, found.position))
+                .collect();
+
+            assert_eq!(digits, vec![(2, 1), (1, 3), (3, 6), (4, 7)]);
+            assert_eq!(DigitScanner::numerals().scan("xtwone3four").len(), 1);
+        }
+
+        #[test]
+        fn test_english_vocabulary() {
+            let scanner = DigitScanner::with_vocabulary(&Vocabulary::english(), false);
+
+            assert_eq!(calibration_value(&scanner, "sevenine"), 79);
+            assert_eq!(calibration_value(&scanner, "SevenNine"), 0);
+
+            let scanner = DigitScanner::with_vocabulary(&Vocabulary::english(), true);
+            assert_eq!(calibration_value(&scanner, "SevenNine"), 79);
+            assert_eq!(calibration_value(&scanner, "x5TWONE"), 51);
+        }
+
+        #[test]
+        fn test_german_vocabulary() {
+            let german = Vocabulary::parse(
+                "# Deutsch\neins = 1\nzwei = 2\ndrei = 3\nvier = 4\nfünf = 5\nsechs = 6\nsieben = 7\nacht = 8\nneun = 9",
+            )
+            .unwrap();
+            let scanner = DigitScanner::with_vocabulary(&german, true);
+
+            assert_eq!(calibration_value(&scanner, "Fünfzehnacht"), 58);
+            assert_eq!(calibration_value(&scanner, "zweins"), 21);
+            assert_eq!(calibration_value(&scanner, "one2three"), 22);
+        }
+
+        #[test]
+        fn test_vocabulary_parse_errors() {
+            let error = Vocabulary::parse("un = 1\ndix = 10").unwrap_err();
+            assert_eq!(error.line, 2);
+
+            let error = Vocabulary::parse("# comment\n\nun 1").unwrap_err();
+            assert_eq!((error.line, error.text.as_str()), (3, "un 1"));
+        }
+
+        #[test]
+        fn test_vocabulary_load() {
+            let french = Vocabulary::load(Path::new("inputs/day1-vocabulary-example.txt")).unwrap();
+            let scanner = DigitScanner::with_vocabulary(&french, false);
+
+            assert_eq!(calibration_value(&scanner, "deuxtroisun"), 21);
+            assert!(matches!(
+                Vocabulary::load(Path::new("inputs/missing-vocabulary.txt")),
+                Err(LoadError::Read { path, .. }) if path == "inputs/missing-vocabulary.txt"
+            ));
+        }
     }
 }
@@ -1,89 +1,79 @@
-use std::vec;
+use crate::{
+    error::ParseError,
+    geometry::{Direction, Point},
+    grid::Grid,
+    solution::Solution,
+};
+use std::collections::{HashMap, HashSet};
 
 #[derive(Debug)]
-struct Map {
-    tiles: Vec<Vec<Tile>>,
+pub struct Map {
+    pub tiles: Grid<Tile>,
 }
 
 impl Map {
-    fn parse(lines: &str) -> Self {
+    pub fn parse(lines: &str) -> Result<Self, ParseError> {
         // Pad the graph with ground
-        let line_length = lines.lines().next().map_or(0, |line| line.len() + 2); // +2 for the added '.' at the start and end
-        let dot_line = ".".repeat(line_length);
-
-        Self {
-            tiles: std::iter::once(dot_line.clone())
-                .chain(lines.lines().map(|line| format!(".{}.", line)))
-                .chain(std::iter::once(dot_line))
-                .enumerate()
-                .map(|(i, line)| {
-                    line.split("")
-                        .filter(|line| !line.is_empty())
-                        .enumerate()
-                        .map(|(j, v)| Tile::parse(i, j, v))
-                        .collect::<Vec<Tile>>()
-                })
-                .collect(),
-        }
-    }
-
-    fn get(&self, x: usize, y: usize) -> Option<&Tile> {
-        if x > self.tiles.len() - 1 {
-            return None;
-        }
+        let line_length = lines
+            .lines()
+            .next()
+            .map_or(0, |line| line.chars().count() + 2); // +2 for the added '.' at the start and end
+        let height = lines.lines().count() + 2;
+
+        let mut cells: Vec<Tile> = (0..line_length).map(|j| Tile::ground(0, j)).collect();
+        for (i, line) in lines.lines().enumerate() {
+            if line.chars().count() + 2 != line_length {
+                return Err(ParseError::new(
+                    line,
+                    format!("expected {} tiles, found", line_length - 2),
+                )
+                .at(i + 1, 1));
+            }
 
-        let line = &self.tiles[x];
-        if y > line.len() - 1 {
-            return None;
+            cells.push(Tile::ground(i + 1, 0));
+            for (j, v) in line.chars().enumerate() {
+                cells.push(
+                    Tile::parse(i + 1, j + 1, &v.to_string())
+                        .map_err(|error| error.at(i + 1, j + 1))?,
+                );
+            }
+            cells.push(Tile::ground(i + 1, line_length - 1));
         }
+        cells.extend((0..line_length).map(|j| Tile::ground(height - 1, j)));
 
-        Some(&line[y])
+        Ok(Self {
+            tiles: Grid::new(line_length, height, cells),
+        })
     }
 
-    fn find_start(&self) -> (usize, usize) {
-        for (i, line) in self.tiles.iter().enumerate() {
-            for (j, tile) in line.iter().enumerate() {
-                if tile.direction == TileDirection::Start {
-                    return (i, j);
-                }
-            }
-        }
-
-        (0, 0)
+    pub fn get(&self, x: usize, y: usize) -> Option<&Tile> {
+        self.tiles.get(x, y)
     }
 
-    fn next_tiles(
-        &self,
-        x: usize,
-        y: usize,
-        visited: &[(usize, usize, u64)],
-        skip_valid_check: bool,
-    ) -> Vec<&Tile> {
-        let next_coordinates: [(isize, isize); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+    pub fn find_start(&self) -> (usize, usize) {
+        self.tiles
+            .iter()
+            .find(|(_, tile)| tile.direction == TileDirection::Start)
+            .map_or((0, 0), |(position, _)| position)
+    }
 
+    fn next_tiles(&self, x: usize, y: usize, visited: &HashSet<(usize, usize)>) -> Vec<&Tile> {
         let tile = self.get(x, y).unwrap();
         let mut next_tiles: Vec<&Tile> = vec![];
 
-        for (dx, dy) in next_coordinates {
-            if (dx < 0 && x == 0) || (dy < 0 && y == 0) {
+        for direction in Direction::ALL {
+            let Some(next) = self.tiles.step(Point::new(x, y), direction) else {
                 continue;
-            }
-
-            let nx = (x as isize + dx) as usize;
-            let ny = (y as isize + dy) as usize;
+            };
 
-            if visited.iter().any(|(x, y, _)| nx == *x && ny == *y) {
+            if visited.contains(&(next.x, next.y)) {
                 continue;
             }
 
-            if let Some(next_tile) = self.get(nx, ny) {
-                if skip_valid_check
-                    || (tile.is_valid_from(nx as isize, ny as isize)
-                        && next_tile.is_valid_from(x as isize, y as isize))
-                {
-                    next_tiles.push(next_tile);
-                }
-            };
+            let next_tile = &self.tiles[next];
+            if tile.connects(direction) && next_tile.connects(direction.reverse()) {
+                next_tiles.push(next_tile);
+            }
         }
 
         next_tiles
@@ -93,10 +83,10 @@ impl Map {
         let mut count: u64 = 0;
 
         // Mark start tile as visited
-        let mut visited: Vec<(usize, usize, u64)> = vec![(x, y, 0)];
+        let mut visited: HashSet<(usize, usize)> = HashSet::from([(x, y)]);
 
         // Get valid next tile for starting node
-        let mut next_tiles = self.next_tiles(x, y, &visited, false);
+        let mut next_tiles = self.next_tiles(x, y, &visited);
 
         while !next_tiles.is_empty() {
             count += 1;
@@ -105,12 +95,12 @@ impl Map {
 
             // Mark all next tiles as visited
             for next_tile in &next_tiles {
-                visited.push((next_tile.x, next_tile.y, count));
+                visited.insert((next_tile.x, next_tile.y));
             }
 
             // Find all next tiles for iteration
             for next_tile in &next_tiles {
-                new_next_tiles.extend(self.next_tiles(next_tile.x, next_tile.y, &visited, false));
+                new_next_tiles.extend(self.next_tiles(next_tile.x, next_tile.y, &visited));
             }
 
             next_tiles = new_next_tiles;
@@ -119,18 +109,18 @@ impl Map {
         count
     }
 
-    fn get_loop_vertices(&self) -> Vec<(usize, usize)> {
+    pub fn get_loop_vertices(&self) -> Vec<(usize, usize)> {
         let mut vertices: Vec<(usize, usize)> = vec![];
-        let mut visited: Vec<(usize, usize, u64)> = vec![];
+        let mut visited: HashSet<(usize, usize)> = HashSet::new();
 
         let start_node = self.find_start();
         let mut next_node = start_node;
 
         loop {
             vertices.push(next_node);
-            visited.push((next_node.0, next_node.1, 0));
+            visited.insert(next_node);
 
-            let next_nodes = self.next_tiles(next_node.0, next_node.1, &visited, false);
+            let next_nodes = self.next_tiles(next_node.0, next_node.1, &visited);
 
             if next_nodes.is_empty() {
                 break;
@@ -142,233 +132,67 @@ impl Map {
         vertices
     }
 
-    fn populate_enclosed_regions(&self, debug: bool) -> u64 {
-        let anchor_points: Vec<(usize, usize)> = self.get_loop_vertices();
-
-        let loop_tiles = anchor_points.iter().map(|(x, y)| self.get(*x, *y).unwrap());
-
-        let mut tiles: Vec<Vec<String>> = self
-            .tiles
+    // Twice the area of the polygon traced by the loop, by the shoelace formula
+    pub fn doubled_loop_area(&self) -> u64 {
+        let vertices = self.get_loop_vertices();
+        let doubled: i64 = vertices
             .iter()
-            .map(|line| line.iter().map(|tile| tile.value.clone()).collect())
-            .collect();
-
-        if debug {
-            println!("Raw tiles:");
-            self.print(&tiles);
-        }
-
-        // Traverse the graph and assign a counter to each tile
-        for (i, tile) in loop_tiles.clone().enumerate() {
-            tiles[tile.x][tile.y] = (i + 1).to_string();
-        }
-
-        if debug {
-            println!("Tiles with counter:");
-            self.print(&tiles);
-        }
+            .zip(vertices.iter().cycle().skip(1))
+            .map(|(&(x1, y1), &(x2, y2))| x1 as i64 * y2 as i64 - x2 as i64 * y1 as i64)
+            .sum();
 
-        // Replace all non-loop tiles with ground (.)
-        for (x, lines) in tiles.clone().iter().enumerate() {
-            for (y, _) in lines.iter().enumerate() {
-                if !loop_tiles.clone().any(|tile| tile.x == x && tile.y == y) {
-                    tiles[x][y] = ".".to_string();
-                }
-            }
-        }
-
-        if debug {
-            println!("Replace junk with ground:");
-            self.print(&tiles);
-        }
-
-        // Expand the graph, add ground between all rows and colums
-        let mut expanded_tiles: Vec<Vec<String>> = vec![];
-        for x in 0..tiles.len() {
-            let mut row: Vec<String> = vec![];
-            for y in 0..tiles[x].len() {
-                if let Ok(value) = tiles[x][y].parse::<u64>() {
-                    row.push((value * 2).to_string());
-                } else {
-                    row.push(tiles[x][y].clone());
-                }
-                row.push(".".to_string());
-            }
-
-            let expanded_row_len = row.len();
-            expanded_tiles.push(row);
-            if x < tiles.len() - 1 {
-                expanded_tiles.push((0..expanded_row_len).map(|_| ".".to_string()).collect());
-            }
-        }
-
-        if debug {
-            println!("Expanded tiles:");
-            self.print(&expanded_tiles);
-        }
-
-        // Fill in missing tiles in the loop
-        // let next_coordinates: [(isize, isize); 4] = [(-2, 0), (2, 0), (0, -2), (0, 2)];
-        let mut max_val: i64 = 0;
-        let mut min_val: i64 = 999999;
-        for x in 0..expanded_tiles.len() {
-            for y in 0..expanded_tiles[x].len() {
-                if let Ok(val) = expanded_tiles[x][y].parse::<i64>() {
-                    if val > max_val {
-                        max_val = val;
-                    }
-
-                    if val < min_val {
-                        min_val = val;
-                    }
-                }
-                let left = if y > 0 {
-                    expanded_tiles[x][y - 1].parse::<u64>().unwrap_or(0) as i64
-                } else {
-                    0
-                };
-                let right = if y < expanded_tiles[x].len() - 1 {
-                    expanded_tiles[x][y + 1].parse::<u64>().unwrap_or(0) as i64
-                } else {
-                    0
-                };
-                let up = if x > 0 {
-                    expanded_tiles[x - 1][y].parse::<u64>().unwrap_or(0) as i64
-                } else {
-                    0
-                };
-                let down = if x < expanded_tiles.len() - 1 {
-                    expanded_tiles[x + 1][y].parse::<u64>().unwrap_or(0) as i64
-                } else {
-                    0
-                };
-
-                if left > 0 && right > 0 && (left - right == -2 || left - right == 2) {
-                    expanded_tiles[x][y] = (left.min(right) + 1).to_string();
-                } else if up > 0 && down > 0 && (up - down == -2 || up - down == 2) {
-                    expanded_tiles[x][y] = (up.min(down) + 1).to_string();
-                }
-            }
-        }
-
-        let minxmax = min_val + max_val;
-        for x in 0..expanded_tiles.len() {
-            for y in 0..expanded_tiles[x].len() {
-                let left = if y > 0 {
-                    expanded_tiles[x][y - 1].parse::<u64>().unwrap_or(0) as i64
-                } else {
-                    0
-                };
-                let right = if y < expanded_tiles[x].len() - 1 {
-                    expanded_tiles[x][y + 1].parse::<u64>().unwrap_or(0) as i64
-                } else {
-                    0
-                };
-                let up = if x > 0 {
-                    expanded_tiles[x - 1][y].parse::<u64>().unwrap_or(0) as i64
-                } else {
-                    0
-                };
-                let down = if x < expanded_tiles.len() - 1 {
-                    expanded_tiles[x + 1][y].parse::<u64>().unwrap_or(0) as i64
-                } else {
-                    0
-                };
-
-                if left + right == minxmax || up + down == minxmax {
-                    expanded_tiles[x][y] = (min_val - 1).to_string();
-                }
-            }
-        }
-
-        if debug {
-            println!("Expanded counted tiles:");
-            self.print(&expanded_tiles);
-        }
-
-        // Traverse the graph and mark nodes outside the loop
-        let mut next_nodes: Vec<(usize, usize)> = vec![(0, 0)];
-        while let Some((x, y)) = next_nodes.pop() {
-            let next_coordinates: [(isize, isize); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
-            for (dx, dy) in next_coordinates {
-                if (dx < 0 && x == 0) || (dy < 0 && y == 0) {
-                    continue;
-                }
-
-                let nx = x as isize + dx;
-                let ny = y as isize + dy;
+        doubled.unsigned_abs()
+    }
 
-                if nx < 0
-                    || ny < 0
-                    || nx > expanded_tiles.len() as isize - 1
-                    || ny > expanded_tiles[0].len() as isize - 1
-                {
-                    continue;
-                }
+    // Pick's theorem, A = I + B / 2 - 1, with every loop tile as a boundary point B. A path from
+    // `S` that never closes has too little area for its length, and encloses nothing.
+    pub fn enclosed_by_pick(&self) -> u64 {
+        let boundary = self.get_loop_vertices().len() as u64;
 
-                if expanded_tiles[nx as usize][ny as usize] == "." {
-                    next_nodes.push((nx as usize, ny as usize));
-                }
-            }
-
-            expanded_tiles[x][y] = "O".to_string();
-        }
+        (self.doubled_loop_area() + 2)
+            .checked_sub(boundary)
+            .map_or(0, |twice_interior| twice_interior / 2)
+    }
 
-        if debug {
-            println!("Outside tiles marked:");
-            self.print(&expanded_tiles);
-        }
+    // Tiles inside the loop, found by scanning each row and flipping between outside and inside
+    // at every loop tile joined to the loop tile above it. Positions are those of `get`, which
+    // match the input's 1-based line and column because of the ground padding.
+    pub fn enclosed_tiles(&self) -> Vec<(usize, usize)> {
+        let vertices = self.get_loop_vertices();
+        let order: HashMap<(usize, usize), usize> = vertices
+            .iter()
+            .enumerate()
+            .map(|(index, &position)| (position, index))
+            .collect();
 
-        // Shrink graph back to original size
-        let mut shrunk_tiles: Vec<Vec<String>> = vec![];
-        for (x, rows) in expanded_tiles.iter().enumerate() {
-            let mut shrunk_row = vec![];
-            for (y, row) in rows.iter().enumerate() {
-                if y % 2 == 0 {
-                    shrunk_row.push(row.clone());
-                }
-            }
+        // Neighbouring tiles are joined when they follow each other around the loop
+        let joined_up = |(x, y): (usize, usize)| -> bool {
+            let (Some(&here), Some(&above)) = (order.get(&(x, y)), order.get(&(x - 1, y))) else {
+                return false;
+            };
 
-            if x % 2 == 0 {
-                shrunk_tiles.push(shrunk_row);
-            }
-        }
+            here.abs_diff(above) == 1 || here.abs_diff(above) == vertices.len() - 1
+        };
 
-        if debug {
-            println!("Shrunk graph:");
-            self.print(&shrunk_tiles);
-        }
+        let mut enclosed = vec![];
+        for x in 1..self.tiles.height() {
+            let mut inside = false;
 
-        let mut enclosed_points: Vec<(usize, usize)> = vec![];
-        for (x, rows) in shrunk_tiles.iter().enumerate() {
-            for (y, row) in rows.iter().enumerate() {
-                if row == "." {
-                    enclosed_points.push((x, y));
+            for y in 0..self.tiles.width() {
+                if order.contains_key(&(x, y)) {
+                    inside ^= joined_up((x, y));
+                } else if inside {
+                    enclosed.push((x, y));
                 }
             }
         }
 
-        // Count all ground tiles that are left
-        shrunk_tiles
-            .iter()
-            .flatten()
-            .filter(|value| *value == ".")
-            .count() as u64
-    }
-
-    fn print(&self, tiles: &Vec<Vec<String>>) {
-        for line in tiles {
-            for tile in line {
-                print!("{:>4}", tile);
-            }
-            println!();
-        }
-        println!();
+        enclosed
     }
 }
 
 #[derive(Debug, Eq, PartialEq, Clone)]
-enum TileDirection {
+pub enum TileDirection {
     Start,
     Ground,
     Vertical,
@@ -380,16 +204,16 @@ enum TileDirection {
 }
 
 #[derive(Debug, Clone)]
-struct Tile {
-    x: usize,
-    y: usize,
-    value: String,
-    direction: TileDirection,
+pub struct Tile {
+    pub x: usize,
+    pub y: usize,
+    pub value: String,
+    pub direction: TileDirection,
 }
 
 impl Tile {
-    fn parse(x: usize, y: usize, value: &str) -> Self {
-        Self {
+    pub fn parse(x: usize, y: usize, value: &str) -> Result<Self, ParseError> {
+        Ok(Self {
             x,
             y,
             value: value.to_string(),
@@ -402,62 +226,81 @@ impl Tile {
                 "J" => TileDirection::BendNW,
                 "7" => TileDirection::BendSW,
                 "F" => TileDirection::BendSE,
-                _ => panic!("Incorrect tile direction {}", value),
+                _ => return Err(ParseError::new(value, "expected a pipe or ground, found")),
             },
+        })
+    }
+
+    pub fn ground(x: usize, y: usize) -> Self {
+        Self {
+            x,
+            y,
+            value: ".".to_string(),
+            direction: TileDirection::Ground,
         }
     }
 
-    fn is_valid_from(&self, prev_x: isize, prev_y: isize) -> bool {
-        let curr_x = self.x as isize;
-        let curr_y = self.y as isize;
+    // Whether the pipe has an opening towards `direction`. The start tile connects to anything.
+    pub fn connects(&self, direction: Direction) -> bool {
+        use Direction::*;
 
         match self.direction {
-            TileDirection::Vertical => {
-                curr_y == prev_y && (curr_x == prev_x + 1 || curr_x == prev_x - 1)
-            }
-            TileDirection::Horizontal => {
-                curr_x == prev_x && (curr_y == prev_y + 1 || curr_y == prev_y - 1)
-            }
-            TileDirection::BendNE => {
-                (curr_x == prev_x + 1 && curr_y == prev_y)
-                    || (curr_x == prev_x && curr_y == prev_y - 1)
-            }
-            TileDirection::BendNW => {
-                (curr_x == prev_x && curr_y == prev_y + 1)
-                    || (curr_x == prev_x + 1 && curr_y == prev_y)
-            }
-            TileDirection::BendSW => {
-                (curr_x == prev_x && curr_y == prev_y + 1)
-                    || (curr_x == prev_x - 1 && curr_y == prev_y)
-            }
-            TileDirection::BendSE => {
-                (curr_x == prev_x && curr_y == prev_y - 1)
-                    || (curr_x == prev_x - 1 && curr_y == prev_y)
-            }
+            TileDirection::Vertical => matches!(direction, Up | Down),
+            TileDirection::Horizontal => matches!(direction, Left | Right),
+            TileDirection::BendNE => matches!(direction, Up | Right),
+            TileDirection::BendNW => matches!(direction, Up | Left),
+            TileDirection::BendSW => matches!(direction, Down | Left),
+            TileDirection::BendSE => matches!(direction, Down | Right),
             TileDirection::Start => true,
             TileDirection::Ground => false,
         }
     }
 }
 
-pub mod part1 {
-    use super::*;
-    use std::fs;
+pub struct Day10;
 
-    fn steps_to_furthest_tile(contents: &str) -> u64 {
-        let map = Map::parse(contents);
+impl Solution for Day10 {
+    type Input = Map;
+    type Answer1 = u64;
+    type Answer2 = u64;
 
-        let (x, y) = map.find_start();
+    fn parse(contents: &str) -> Result<Self::Input, ParseError> {
+        Map::parse(contents)
+    }
 
-        map.traverse_from_loop(x, y)
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1::steps_to_furthest_tile(input)
     }
 
-    pub fn run() {
-        let contents = fs::read_to_string("inputs/day10.txt").expect("File not found");
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2::count_enclosed_tiles(input)
+    }
+
+    fn report(input: &Self::Input) -> Option<String> {
+        let enclosed = input.enclosed_tiles();
+        let mut report = format!(
+            "Loop: {} tiles, shoelace area {}\nEnclosed: {} by Pick's theorem, {} by scanline\n",
+            input.get_loop_vertices().len(),
+            input.doubled_loop_area() as f64 / 2.0,
+            input.enclosed_by_pick(),
+            enclosed.len()
+        );
+
+        for (line, column) in enclosed {
+            report += &format!("line {}, column {}\n", line, column);
+        }
+
+        Some(report)
+    }
+}
+
+pub mod part1 {
+    use super::*;
 
-        let result = steps_to_furthest_tile(&contents);
+    pub fn steps_to_furthest_tile(map: &Map) -> u64 {
+        let (x, y) = map.find_start();
 
-        println!("Day 10 Part 1: {}", result);
+        map.traverse_from_loop(x, y)
     }
 
     #[cfg(test)]
@@ -467,7 +310,7 @@ pub mod part1 {
         #[test]
         fn test_day10_part1a() {
             assert_eq!(
-                steps_to_furthest_tile(".....\n.S-7.\n.|.|.\n.L-J.\n....."),
+                steps_to_furthest_tile(&Day10::parse(".....\n.S-7.\n.|.|.\n.L-J.\n.....").unwrap()),
                 4
             );
         }
@@ -475,7 +318,7 @@ pub mod part1 {
         #[test]
         fn test_day10_part1b() {
             assert_eq!(
-                steps_to_furthest_tile(".....\n.F-7.\n.|.|.\n.L-S.\n....."),
+                steps_to_furthest_tile(&Day10::parse(".....\n.F-7.\n.|.|.\n.L-S.\n.....").unwrap()),
                 4
             );
         }
@@ -483,7 +326,7 @@ pub mod part1 {
         #[test]
         fn test_day10_part1c() {
             assert_eq!(
-                steps_to_furthest_tile(".....\n.F-S.\n.|.|.\n.L-J.\n....."),
+                steps_to_furthest_tile(&Day10::parse(".....\n.F-S.\n.|.|.\n.L-J.\n.....").unwrap()),
                 4
             );
         }
@@ -491,7 +334,7 @@ pub mod part1 {
         #[test]
         fn test_day10_part1d() {
             assert_eq!(
-                steps_to_furthest_tile(".....\n.F-7.\n.|.|.\n.S-J.\n....."),
+                steps_to_furthest_tile(&Day10::parse(".....\n.F-7.\n.|.|.\n.S-J.\n.....").unwrap()),
                 4
             );
         }
@@ -499,7 +342,7 @@ pub mod part1 {
         #[test]
         fn test_day10_part1e() {
             assert_eq!(
-                steps_to_furthest_tile("..F7.\n.FJ|.\nSJ.L7\n|F--J\nLJ..."),
+                steps_to_furthest_tile(&Day10::parse("..F7.\n.FJ|.\nSJ.L7\n|F--J\nLJ...").unwrap()),
                 8
             );
         }
@@ -507,20 +350,23 @@ pub mod part1 {
         #[test]
         fn test_day10_part1f() {
             assert_eq!(
-                steps_to_furthest_tile("7-F7-\n.FJ|7\nSJLL7\n|F--J\nLJ.LJ"),
+                steps_to_furthest_tile(&Day10::parse("7-F7-\n.FJ|7\nSJLL7\n|F--J\nLJ.LJ").unwrap()),
                 8
             );
         }
 
         #[test]
         fn test_day10_part1g() {
-            assert_eq!(steps_to_furthest_tile("S-7\n|.|\nL-J"), 4);
+            assert_eq!(
+                steps_to_furthest_tile(&Day10::parse("S-7\n|.|\nL-J").unwrap()),
+                4
+            );
         }
 
         #[test]
         fn test_day10_part1h() {
             assert_eq!(
-                steps_to_furthest_tile("-L|F7\n7S-7|\nL|7||\n-L-J|\nL|-JF"),
+                steps_to_furthest_tile(&Day10::parse("-L|F7\n7S-7|\nL|7||\n-L-J|\nL|-JF").unwrap()),
                 4
             );
         }
@@ -528,7 +374,7 @@ pub mod part1 {
         #[test]
         fn test_day10_part1i() {
             assert_eq!(
-                steps_to_furthest_tile("-L|F7\n7S-7|\nL|7||\n-L-J|\nL|-JF"),
+                steps_to_furthest_tile(&Day10::parse("-L|F7\n7S-7|\nL|7||\n-L-J|\nL|-JF").unwrap()),
                 4
             );
         }
@@ -537,18 +383,9 @@ pub mod part1 {
 
 pub mod part2 {
     use super::*;
-    use std::fs;
 
-    fn count_enclosed_tiles(contents: &str, debug: bool) -> u64 {
-        Map::parse(contents).populate_enclosed_regions(debug)
-    }
-
-    pub fn run() {
-        let contents = fs::read_to_string("inputs/day10.txt").expect("File not found");
-
-        let result = count_enclosed_tiles(&contents, false);
-
-        println!("Day 10 Part 2: {}", result);
+    pub fn count_enclosed_tiles(map: &Map) -> u64 {
+        map.enclosed_by_pick()
     }
 
     #[cfg(test)]
@@ -557,14 +394,21 @@ pub mod part2 {
 
         #[test]
         fn test_day10_part2a() {
-            assert_eq!(count_enclosed_tiles("...........\n.S-------7.\n.|F-----7|.\n.||.....||.\n.||.....||.\n.|L-7.F-J|.\n.|..|.|..|.\n.L--J.L--J.\n...........", true), 4);
+            assert_eq!(count_enclosed_tiles(&Day10::parse("...........\n.S-------7.\n.|F-----7|.\n.||.....||.\n.||.....||.\n.|L-7.F-J|.\n.|..|.|..|.\n.L--J.L--J.\n...........").unwrap()), 4);
+        }
+
+        #[test]
+        fn test_day10_open_path() {
+            let map = Day10::parse("S---.\n.....").unwrap();
+
+            assert_eq!(map.get_loop_vertices().len(), 4);
+            assert_eq!(count_enclosed_tiles(&map), 0);
         }
 
         #[test]
         fn test_day10_part2b() {
             assert_eq!(
-                count_enclosed_tiles(
-                    "..........\n.S------7.\n.|F----7|.\n.||....||.\n.||....||.\n.|L-7F-J|.\n.|..||..|.\n.L--JL--J.\n..........", true
+                count_enclosed_tiles(&Day10::parse("..........\n.S------7.\n.|F----7|.\n.||....||.\n.||....||.\n.|L-7F-J|.\n.|..||..|.\n.L--JL--J.\n..........").unwrap()
                 ),
                 4
             );
@@ -573,9 +417,7 @@ pub mod part2 {
         #[test]
         fn test_day10_part2c() {
             assert_eq!(
-                count_enclosed_tiles(
-                    ".F----7F7F7F7F-7....\n.|F--7||||||||FJ....\n.||.FJ||||||||L7....\nFJL7L7LJLJ||LJ.L-7..\nL--J.L7...LJS7F-7L7.\n....F-J..F7FJ|L7L7L7\n....L7.F7||L7|.L7L7|\n.....|FJLJ|FJ|F7|.LJ\n....FJL-7.||.||||...\n....L---J.LJ.LJLJ..."
-                ,true),
+                count_enclosed_tiles(&Day10::parse(".F----7F7F7F7F-7....\n.|F--7||||||||FJ....\n.||.FJ||||||||L7....\nFJL7L7LJLJ||LJ.L-7..\nL--J.L7...LJS7F-7L7.\n....F-J..F7FJ|L7L7L7\n....L7.F7||L7|.L7L7|\n.....|FJLJ|FJ|F7|.LJ\n....FJL-7.||.||||...\n....L---J.LJ.LJLJ...").unwrap()),
                 8
             );
         }
@@ -583,11 +425,32 @@ pub mod part2 {
         #[test]
         fn test_day10_part2d() {
             assert_eq!(
-                count_enclosed_tiles(
-                    "FF7FSF7F7F7F7F7F---7\nL|LJ||||||||||||F--J\nFL-7LJLJ||||||LJL-77\nF--JF--7||LJLJ7F7FJ-\nL---JF-JLJ.||-FJLJJ7\n|F|F-JF---7F7-L7L|7|\n|FFJF7L7F-JF7|JL---7\n7-L-JL7||F7|L7F-7F7|\nL.L7LFJ|||||FJL7||LJ\nL7JLJL-JLJLJL--JLJ.L"
-                ,true),
+                count_enclosed_tiles(&Day10::parse("FF7FSF7F7F7F7F7F---7\nL|LJ||||||||||||F--J\nFL-7LJLJ||||||LJL-77\nF--JF--7||LJLJ7F7FJ-\nL---JF-JLJ.||-FJLJJ7\n|F|F-JF---7F7-L7L|7|\n|FFJF7L7F-JF7|JL---7\n7-L-JL7||F7|L7F-7F7|\nL.L7LFJ|||||FJL7||LJ\nL7JLJL-JLJLJL--JLJ.L").unwrap()),
                 10
             );
         }
+
+        #[test]
+        fn test_day10_scanline_matches_pick() {
+            for example in [
+                "...........\n.S-------7.\n.|F-----7|.\n.||.....||.\n.||.....||.\n.|L-7.F-J|.\n.|..|.|..|.\n.L--J.L--J.\n...........",
+                ".F----7F7F7F7F-7....\n.|F--7||||||||FJ....\n.||.FJ||||||||L7....\nFJL7L7LJLJ||LJ.L-7..\nL--J.L7...LJS7F-7L7.\n....F-J..F7FJ|L7L7L7\n....L7.F7||L7|.L7L7|\n.....|FJLJ|FJ|F7|.LJ\n....FJL-7.||.||||...\n....L---J.LJ.LJLJ...",
+                "FF7FSF7F7F7F7F7F---7\nL|LJ||||||||||||F--J\nFL-7LJLJ||||||LJL-77\nF--JF--7||LJLJ7F7FJ-\nL---JF-JLJ.||-FJLJJ7\n|F|F-JF---7F7-L7L|7|\n|FFJF7L7F-JF7|JL---7\n7-L-JL7||F7|L7F-7F7|\nL.L7LFJ|||||FJL7||LJ\nL7JLJL-JLJLJL--JLJ.L",
+                "S-7\n|.|\nL-J",
+                "S7\nLJ",
+            ] {
+                let map = Day10::parse(example).unwrap();
+
+                assert_eq!(map.enclosed_tiles().len() as u64, map.enclosed_by_pick());
+            }
+        }
+
+        #[test]
+        fn test_day10_enclosed_tiles() {
+            let map = Day10::parse("...........\n.S-------7.\n.|F-----7|.\n.||.....||.\n.||.....||.\n.|L-7.F-J|.\n.|..|.|..|.\n.L--J.L--J.\n...........").unwrap();
+
+            assert_eq!(map.enclosed_tiles(), vec![(7, 3), (7, 4), (7, 8), (7, 9)]);
+            assert_eq!(map.doubled_loop_area(), 52);
+        }
     }
 }
@@ -1,71 +1,72 @@
-fn manhattan_distance(x1: i64, y1: i64, x2: i64, y2: i64) -> i64 {
-    let dx = (x2 - x1).abs();
-    let dy = (y2 - y1).abs();
-    dx + dy
-}
-pub mod part1 {
-    use super::*;
-    use std::fs;
-
-    fn distance_sum(contents: &str) -> i64 {
-        let matrix: Vec<Vec<&str>> = contents
-            .lines()
-            .map(|line| {
-                line.split("")
-                    .filter(|line| !line.is_empty())
-                    .collect::<Vec<&str>>()
-            })
-            .collect();
-
-        let mut expanded_matrix: Vec<Vec<&str>> = vec![vec![]];
-
-        for line in matrix.iter() {
-            let mut expanded_line: Vec<&str> = vec![];
-
-            for (y, val) in line.iter().enumerate() {
-                let column: Vec<&str> = matrix.iter().map(|l| l[y]).collect();
-                expanded_line.push(val);
-                if column.iter().all(|&val| val == ".") {
-                    expanded_line.push(val);
+use crate::{error::ParseError, geometry::Point, grid::Grid, solution::Solution};
+
+// Galaxy positions after every empty row and column has been replaced by `expand_factor` copies
+pub fn expanded_galaxies(image: &Grid<char>, expand_factor: usize) -> Vec<Point> {
+    let empty_rows: Vec<bool> = image
+        .rows()
+        .map(|row| row.iter().all(|&val| val == '.'))
+        .collect();
+    let empty_columns: Vec<bool> = image
+        .columns()
+        .map(|column| column.iter().all(|&&val| val == '.'))
+        .collect();
+
+    let offsets = |empty: &[bool]| -> Vec<usize> {
+        empty
+            .iter()
+            .scan(0, |extra, &is_empty| {
+                if is_empty {
+                    *extra += expand_factor - 1;
                 }
-            }
+                Some(*extra)
+            })
+            .collect()
+    };
+    let (x_extra, y_extra) = (offsets(&empty_rows), offsets(&empty_columns));
+
+    image
+        .iter()
+        .filter(|(_, &val)| val == '#')
+        .map(|((x, y), _)| Point::new(x + x_extra[x], y + y_extra[y]))
+        .collect()
+}
 
-            expanded_matrix.push(expanded_line.clone());
-            if line.iter().all(|&val| val == ".") {
-                expanded_matrix.push(expanded_line.clone());
-            }
+pub fn pair_distance_sum(galaxies: &[Point]) -> i64 {
+    let mut sum = 0;
+    for i in 0..galaxies.len() {
+        for j in i + 1..galaxies.len() {
+            sum += galaxies[i].manhattan_distance(&galaxies[j]);
         }
+    }
 
-        let mut galaxies: Vec<(usize, usize)> = vec![];
-        for (x, line) in expanded_matrix.iter().enumerate() {
-            for (y, val) in line.iter().enumerate() {
-                if *val == "#" || val.parse::<i64>().is_ok() {
-                    galaxies.push((x, y));
-                }
-            }
-        }
+    sum as i64
+}
 
-        let mut edges: Vec<((usize, usize), (usize, usize))> = vec![];
-        for i in 0..galaxies.len() {
-            for j in i..galaxies.len() {
-                edges.push((galaxies[i], galaxies[j]));
-            }
-        }
+pub struct Day11;
 
-        let mut sum = 0;
-        for ((x1, y1), (x2, y2)) in edges {
-            sum += manhattan_distance(x1 as i64, y1 as i64, x2 as i64, y2 as i64);
-        }
+impl Solution for Day11 {
+    type Input = Grid<char>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(contents: &str) -> Result<Self::Input, ParseError> {
+        Grid::parse(contents, |c| matches!(c, '.' | '#').then_some(c))
+    }
 
-        sum
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1::distance_sum(input)
     }
 
-    pub fn run() {
-        let contents = fs::read_to_string("inputs/day11.txt").expect("File not found");
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2::distance_sum(input, 1000000)
+    }
+}
 
-        let result = distance_sum(&contents);
+pub mod part1 {
+    use super::*;
 
-        println!("Day 11 Part 1: {}", result);
+    pub fn distance_sum(image: &Grid<char>) -> i64 {
+        pair_distance_sum(&expanded_galaxies(image, 2))
     }
 
     #[cfg(test)]
@@ -74,80 +75,16 @@ pub mod part1 {
 
         #[test]
         fn test_day11_part1() {
-            assert_eq!(distance_sum("...#......\n.......#..\n#.........\n..........\n......#...\n.#........\n.........#\n..........\n.......#..\n#...#....."), 374);
+            assert_eq!(distance_sum(&Day11::parse("...#......\n.......#..\n#.........\n..........\n......#...\n.#........\n.........#\n..........\n.......#..\n#...#.....").unwrap()), 374);
         }
     }
 }
 
 pub mod part2 {
     use super::*;
-    use std::{collections::HashMap, fs};
-
-    fn distance_sum(contents: &str, expand_factor: usize) -> i64 {
-        let matrix: Vec<Vec<&str>> = contents
-            .lines()
-            .map(|line| {
-                line.split("")
-                    .filter(|line| !line.is_empty())
-                    .collect::<Vec<&str>>()
-            })
-            .collect();
-
-        let mut multipliers_x: HashMap<usize, usize> = HashMap::new();
-        let mut multipliers_y: HashMap<usize, usize> = HashMap::new();
-
-        for (x, line) in matrix.iter().enumerate() {
-            let should_multiply_line = line.iter().all(|&val| val == ".");
-
-            for (y, _) in line.iter().enumerate() {
-                let column: Vec<&str> = matrix.iter().map(|l| l[y]).collect();
-                let should_multiply_column = column.iter().all(|&val| val == ".");
-
-                if should_multiply_line {
-                    multipliers_x.insert(x, expand_factor);
-                }
-
-                if should_multiply_column {
-                    multipliers_y.insert(y, expand_factor);
-                }
-            }
-        }
-
-        let mut galaxies: Vec<(usize, usize)> = vec![];
-        let mut x_extra = 0;
-        for (x, line) in matrix.iter().enumerate() {
-            x_extra += *multipliers_x.get(&x).unwrap_or(&1) - 1;
-
-            let mut y_extra = 0;
-            for (y, val) in line.iter().enumerate() {
-                y_extra += *multipliers_y.get(&y).unwrap_or(&1) - 1;
-                if *val == "#" || val.parse::<i64>().is_ok() {
-                    galaxies.push((x + x_extra, y + y_extra));
-                }
-            }
-        }
-
-        let mut edges: Vec<((usize, usize), (usize, usize))> = vec![];
-        for i in 0..galaxies.len() {
-            for j in i..galaxies.len() {
-                edges.push((galaxies[i], galaxies[j]));
-            }
-        }
-
-        let mut sum: i64 = 0;
-        for ((x1, y1), (x2, y2)) in edges {
-            sum += manhattan_distance(x1 as i64, y1 as i64, x2 as i64, y2 as i64);
-        }
-
-        sum
-    }
-
-    pub fn run() {
-        let contents = fs::read_to_string("inputs/day11.txt").expect("File not found");
-
-        let result = distance_sum(&contents, 1000000);
 
-        println!("Day 11 Part 2: {}", result);
+    pub fn distance_sum(image: &Grid<char>, expand_factor: usize) -> i64 {
+        pair_distance_sum(&expanded_galaxies(image, expand_factor))
     }
 
     #[cfg(test)]
@@ -156,7 +93,7 @@ pub mod part2 {
 
         #[test]
         fn test_day11_part2() {
-            assert_eq!(distance_sum("...#......\n.......#..\n#.........\n..........\n......#...\n.#........\n.........#\n..........\n.......#..\n#...#.....", 100), 8410);
+            assert_eq!(distance_sum(&Day11::parse("...#......\n.......#..\n#.........\n..........\n......#...\n.#........\n.........#\n..........\n.......#..\n#...#.....").unwrap(), 100), 8410);
         }
     }
 }
@@ -1,86 +1,127 @@
-pub mod part1 {
-    use std::fs;
+use crate::{
+    error::{number, parse_lines, split_once, ParseError},
+    solution::Solution,
+};
+use std::collections::HashMap;
+
+#[derive(Debug)]
+pub struct Record {
+    pub springs: Vec<char>,
+    pub groups: Vec<usize>,
+}
 
-    use regex::Regex;
+impl Record {
+    pub fn parse(line: &str) -> Result<Self, ParseError> {
+        let (springs, groups) = split_once(line, " ")?;
+        if let Some(c) = springs.chars().find(|c| !matches!(c, '.' | '#' | '?')) {
+            return Err(ParseError::new(
+                &c.to_string(),
+                "expected `.`, `#` or `?`, found",
+            ));
+        }
 
-    fn generate_combos(chars: Vec<&str>) -> Vec<Vec<&str>> {
-        let mut combos: Vec<Vec<&str>> = vec![];
-        if let Some(next_unknown) = chars.iter().position(|&c| c == "?") {
-            let mut as_broken = chars.clone();
-            as_broken[next_unknown] = "#";
-            combos.push(as_broken.clone());
+        Ok(Self {
+            springs: springs.chars().collect(),
+            groups: groups
+                .split(',')
+                .filter(|s| !s.is_empty())
+                .map(number)
+                .collect::<Result<_, _>>()?,
+        })
+    }
 
-            let mut as_working = chars.clone();
-            as_working[next_unknown] = ".";
-            combos.push(as_working.clone());
+    pub fn unfold(&self, times: usize) -> Self {
+        let mut springs = self.springs.clone();
+        for _ in 1..times {
+            springs.push('?');
+            springs.extend(&self.springs);
+        }
 
-            combos.extend(generate_combos(as_broken));
-            combos.extend(generate_combos(as_working));
+        Self {
+            springs,
+            groups: self.groups.repeat(times),
         }
+    }
+
+    pub fn count_arrangements(&self) -> u64 {
+        let mut memo: HashMap<(usize, usize, usize), u64> = HashMap::new();
 
-        combos
-            .iter()
-            .filter(|chars| !chars.iter().any(|&c| c == "?"))
-            .cloned() // This clones each Vec<&str> from &Vec<&str>
-            .collect::<Vec<Vec<&str>>>()
+        self.count_from(0, 0, 0, &mut memo)
     }
 
-    fn is_valid(combo: &str, num_broken: &Vec<usize>) -> bool {
-        let re = Regex::new(r"(#+)").unwrap();
-        let groups: Vec<&str> = re.find_iter(combo).map(|m| m.as_str()).collect();
+    // Counts arrangements of springs[position..], given that `group` groups are already
+    // complete and the current group has `run` broken springs so far
+    fn count_from(
+        &self,
+        position: usize,
+        group: usize,
+        run: usize,
+        memo: &mut HashMap<(usize, usize, usize), u64>,
+    ) -> u64 {
+        if position == self.springs.len() {
+            let all_closed = group == self.groups.len() && run == 0;
+            let last_closing = group + 1 == self.groups.len() && run == self.groups[group];
+
+            return if all_closed || last_closing { 1 } else { 0 };
+        }
 
-        if groups.len() != num_broken.len() {
-            return false;
+        if let Some(&count) = memo.get(&(position, group, run)) {
+            return count;
         }
 
-        for (i, group) in groups.iter().enumerate() {
-            if i > num_broken.len() - 1 {
-                return false;
-            }
+        let spring = self.springs[position];
+        let mut count = 0;
 
-            if group.len() != num_broken[i] {
-                return false;
+        // Treat the spring as broken, extending the current group
+        if (spring == '#' || spring == '?') && group < self.groups.len() && run < self.groups[group]
+        {
+            count += self.count_from(position + 1, group, run + 1, memo);
+        }
+
+        // Treat the spring as working, closing the current group if one is open
+        if spring == '.' || spring == '?' {
+            if run == 0 {
+                count += self.count_from(position + 1, group, 0, memo);
+            } else if run == self.groups[group] {
+                count += self.count_from(position + 1, group + 1, 0, memo);
             }
         }
 
-        true
-    }
+        memo.insert((position, group, run), count);
 
-    fn count_arrangements(line: &str) -> usize {
-        let (p1, p2) = line.split_once(' ').unwrap();
+        count
+    }
+}
 
-        let chars: Vec<&str> = p1.split("").filter(|s| !s.is_empty()).collect();
-        let num_broken: Vec<usize> = p2
-            .split(',')
-            .filter(|s| !s.is_empty())
-            .map(|num| num.parse::<usize>().unwrap())
-            .collect();
+pub struct Day12;
 
-        let mut count = 0;
-        let combos = generate_combos(chars);
-        for combo in combos {
-            if is_valid(combo.join("").as_str(), &num_broken) {
-                count += 1;
-            }
-        }
+impl Solution for Day12 {
+    type Input = Vec<Record>;
+    type Answer1 = u64;
+    type Answer2 = u64;
 
-        count
+    fn parse(contents: &str) -> Result<Self::Input, ParseError> {
+        parse_lines(contents, Record::parse)
     }
 
-    fn sum_arrangements(contents: &str) -> usize {
-        let mut count = 0;
-        for line in contents.lines() {
-            count += count_arrangements(line);
-        }
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1::sum_arrangements(input)
+    }
 
-        count
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2::sum_arrangements(input)
     }
+}
 
-    pub fn run() {
-        let contents = fs::read_to_string("inputs/day12.txt").expect("File not found");
+pub mod part1 {
+    use super::*;
 
-        let result = sum_arrangements(&contents);
-        println!("Day 12 Part 1: {}", result);
+    pub fn count_arrangements(record: &Record) -> u64 {
+        record.count_arrangements()
+    }
+
+    pub fn sum_arrangements(records: &[Record]) -> u64 {
+        records.iter().map(count_arrangements).sum()
     }
 
     #[cfg(test)]
@@ -89,49 +130,63 @@ pub mod part1 {
 
         #[test]
         fn test_day12_part1a() {
-            assert_eq!(count_arrangements("???.### 1,1,3"), 1);
+            assert_eq!(
+                count_arrangements(&Record::parse("???.### 1,1,3").unwrap()),
+                1
+            );
         }
 
         #[test]
         fn test_day12_part1b() {
-            assert_eq!(count_arrangements(".??..??...?##. 1,1,3"), 4);
+            assert_eq!(
+                count_arrangements(&Record::parse(".??..??...?##. 1,1,3").unwrap()),
+                4
+            );
         }
 
         #[test]
         fn test_day12_part1c() {
-            assert_eq!(count_arrangements("?#?#?#?#?#?#?#? 1,3,1,6"), 1);
+            assert_eq!(
+                count_arrangements(&Record::parse("?#?#?#?#?#?#?#? 1,3,1,6").unwrap()),
+                1
+            );
         }
 
         #[test]
         fn test_day12_part1d() {
-            assert_eq!(count_arrangements("????.#...#... 4,1,1"), 1);
+            assert_eq!(
+                count_arrangements(&Record::parse("????.#...#... 4,1,1").unwrap()),
+                1
+            );
         }
 
         #[test]
         fn test_day12_part1e() {
-            assert_eq!(count_arrangements("????.######..#####. 1,6,5"), 4);
+            assert_eq!(
+                count_arrangements(&Record::parse("????.######..#####. 1,6,5").unwrap()),
+                4
+            );
         }
 
         #[test]
         fn test_day12_part1f() {
-            assert_eq!(count_arrangements("?###???????? 3,2,1"), 10);
+            assert_eq!(
+                count_arrangements(&Record::parse("?###???????? 3,2,1").unwrap()),
+                10
+            );
         }
     }
 }
 
 pub mod part2 {
-    use std::fs;
+    use super::*;
 
-    fn do_stuff(contents: &str) -> i32 {
-        contents.len() as i32
+    pub fn count_arrangements(record: &Record) -> u64 {
+        record.unfold(5).count_arrangements()
     }
 
-    pub fn run() {
-        let contents = fs::read_to_string("inputs/day12.txt").expect("File not found");
-
-        let result = do_stuff(&contents);
-
-        println!("Day 12 Part 2: {}", result);
+    pub fn sum_arrangements(records: &[Record]) -> u64 {
+        records.iter().map(count_arrangements).sum()
     }
 
     #[cfg(test)]
@@ -139,8 +194,66 @@ pub mod part2 {
         use super::*;
 
         #[test]
-        fn test_day12_part2() {
-            assert_eq!(do_stuff(""), 0);
+        fn test_day12_part2a() {
+            assert_eq!(
+                count_arrangements(&Record::parse("???.### 1,1,3").unwrap()),
+                1
+            );
+        }
+
+        #[test]
+        fn test_day12_part2b() {
+            assert_eq!(
+                count_arrangements(&Record::parse(".??..??...?##. 1,1,3").unwrap()),
+                16384
+            );
+        }
+
+        #[test]
+        fn test_day12_part2c() {
+            assert_eq!(
+                count_arrangements(&Record::parse("?#?#?#?#?#?#?#? 1,3,1,6").unwrap()),
+                1
+            );
+        }
+
+        #[test]
+        fn test_day12_part2d() {
+            assert_eq!(
+                count_arrangements(&Record::parse("????.#...#... 4,1,1").unwrap()),
+                16
+            );
+        }
+
+        #[test]
+        fn test_day12_part2e() {
+            assert_eq!(
+                count_arrangements(&Record::parse("????.######..#####. 1,6,5").unwrap()),
+                2500
+            );
+        }
+
+        #[test]
+        fn test_day12_part2f() {
+            assert_eq!(
+                count_arrangements(&Record::parse("?###???????? 3,2,1").unwrap()),
+                506250
+            );
+        }
+
+        #[test]
+        fn test_day12_part2g() {
+            assert_eq!(
+                sum_arrangements(&Day12::parse("???.### 1,1,3\n.??..??...?##. 1,1,3\n?#?#?#?#?#?#?#? 1,3,1,6\n????.#...#... 4,1,1\n????.######..#####. 1,6,5\n?###???????? 3,2,1").unwrap()),
+                525152
+            );
+        }
+
+        #[test]
+        fn test_day12_part2_unfold() {
+            let record = Record::parse(".# 1").unwrap().unfold(5);
+            assert_eq!(record.springs.iter().collect::<String>(), ".#?.#?.#?.#?.#");
+            assert_eq!(record.groups, vec![1, 1, 1, 1, 1]);
         }
     }
 }
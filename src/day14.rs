@@ -1,63 +1,60 @@
-fn count_spaces_before(col: &Vec<&str>, mut start: isize) -> usize {
-    let mut count = 0;
-
-    while start >= 0 {
-        if col[start as usize] == "#" {
-            break;
-        }
-
-        if col[start as usize] == "." {
-            count += 1;
+use crate::{error::ParseError, grid::Grid, solution::Solution};
+
+// Rolls every round rock as far north as it goes, stopping at cube rocks and other round rocks
+pub fn tilt_north(platform: &mut Grid<char>) {
+    for column in 0..platform.width() {
+        let mut free = 0;
+
+        for row in 0..platform.height() {
+            match platform[(row, column)] {
+                '#' => free = row + 1,
+                'O' => {
+                    platform[(row, column)] = '.';
+                    platform[(free, column)] = 'O';
+                    free += 1;
+                }
+                _ => {}
+            }
         }
-
-        start -= 1;
     }
+}
 
-    count
+pub fn north_load(platform: &Grid<char>) -> usize {
+    platform
+        .iter()
+        .filter(|(_, &key)| key == 'O')
+        .map(|((row, _), _)| platform.height() - row)
+        .sum()
 }
 
-pub mod part1 {
-    use super::*;
-    use std::fs;
-
-    fn calculate_load(contents: &str) -> usize {
-        let mut count = 0;
-
-        let matrix: Vec<Vec<&str>> = contents
-            .lines()
-            .map(|line| {
-                line.split("")
-                    .filter(|line: &&str| !line.is_empty())
-                    .collect::<Vec<&str>>()
-            })
-            .collect();
-
-        let mut cols = vec![];
-        for y in 0..matrix[0].len() {
-            let mut col: Vec<&str> = vec![];
-            for x in 0..matrix.len() {
-                col.push(matrix[x][y]);
-            }
-            cols.push(col);
-        }
+pub struct Day14;
 
-        for col in &cols {
-            for (i, key) in col.iter().enumerate() {
-                if *key == "O" {
-                    count += col.len() - i + count_spaces_before(&col, i as isize);
-                }
-            }
-        }
+impl Solution for Day14 {
+    type Input = Grid<char>;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-        count
+    fn parse(contents: &str) -> Result<Self::Input, ParseError> {
+        Grid::parse(contents, |c| matches!(c, '.' | '#' | 'O').then_some(c))
     }
 
-    pub fn run() {
-        let contents = fs::read_to_string("inputs/day14.txt").expect("File not found");
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1::calculate_load(input)
+    }
 
-        let result = calculate_load(&contents);
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2::calculate_cycle_load(input)
+    }
+}
 
-        println!("Day 14 Part 1: {}", result);
+pub mod part1 {
+    use super::*;
+
+    pub fn calculate_load(platform: &Grid<char>) -> usize {
+        let mut platform = platform.clone();
+        tilt_north(&mut platform);
+
+        north_load(&platform)
     }
 
     #[cfg(test)]
@@ -66,110 +63,43 @@ pub mod part1 {
 
         #[test]
         fn test_day14_part1() {
-            assert_eq!(calculate_load("O....#....\nO.OO#....#\n.....##...\nOO.#O....O\n.O.....O#.\nO.#..O.#.#\n..O..#O..O\n.......O..\n#....###..\n#OO..#...."), 136);
+            assert_eq!(calculate_load(&Day14::parse("O....#....\nO.OO#....#\n.....##...\nOO.#O....O\n.O.....O#.\nO.#..O.#.#\n..O..#O..O\n.......O..\n#....###..\n#OO..#....").unwrap()), 136);
         }
     }
 }
 
 pub mod part2 {
     use super::*;
-    use std::fs;
+    use std::collections::HashMap;
 
-    fn rotate_matrix(matrix: &mut Vec<Vec<&str>>) {
-        let n = matrix.len();
+    const CYCLES: usize = 1000000000;
 
-        for i in 0..n {
-            for j in i..n {
-                let temp = matrix[i][j];
-                matrix[i][j] = matrix[j][i];
-                matrix[j][i] = temp;
-            }
+    // Tilts north, west, south and east. Rotating clockwise brings the next side to the north,
+    // and four rotations return the platform to its original orientation.
+    fn cycle(platform: &mut Grid<char>) {
+        for _ in 0..4 {
+            tilt_north(platform);
+            *platform = platform.rotate_clockwise();
         }
-
-        for row in matrix.iter_mut() {
-            row.reverse();
-        }
-    }
-
-    fn tilt<'a>(keys: Vec<&'a str>) -> Vec<&'a str> {
-        let mut new_keys = vec!["."; keys.len()];
-        for (i, key) in keys.iter().enumerate() {
-            if *key == "O" {
-                let new_idx = i - count_spaces_before(&keys, i as isize);
-                new_keys[new_idx] = "O";
-            } else if *key == "#" {
-                new_keys[i] = "#";
-            }
-        }
-
-        new_keys
-    }
-
-    fn cycle(matrix: &mut Vec<Vec<&str>>) {
-        rotate_matrix(matrix);
-        rotate_matrix(matrix);
-        rotate_matrix(matrix);
-
-        for i in 0..matrix.len() {
-            matrix[i] = tilt(matrix[i].clone());
-        }
-        rotate_matrix(matrix);
-        for i in 0..matrix.len() {
-            matrix[i] = tilt(matrix[i].clone());
-        }
-        rotate_matrix(matrix);
-        for i in 0..matrix.len() {
-            matrix[i] = tilt(matrix[i].clone());
-        }
-        rotate_matrix(matrix);
-        for i in 0..matrix.len() {
-            matrix[i] = tilt(matrix[i].clone());
-        }
-        rotate_matrix(matrix);
-        rotate_matrix(matrix);
     }
 
-    fn calculate_cycle_load(contents: &str) -> usize {
-        let mut count = 0;
-        let mut matrix: Vec<Vec<&str>> = contents
-            .lines()
-            .map(|line| {
-                line.split("")
-                    .filter(|line: &&str| !line.is_empty())
-                    .collect::<Vec<&str>>()
-            })
-            .collect();
-
-        for _ in 0..10000 {
-            cycle(&mut matrix);
-        }
-
-        let mut cols = vec![];
-        for y in 0..matrix[0].len() {
-            let mut col: Vec<&str> = vec![];
-            for x in 0..matrix.len() {
-                col.push(matrix[x][y]);
-            }
-            cols.push(col);
-        }
+    pub fn calculate_cycle_load(platform: &Grid<char>) -> usize {
+        let mut platform = platform.clone();
 
-        for col in &cols {
-            for (i, key) in col.iter().enumerate() {
-                if *key == "O" {
-                    count += col.len() - i;
+        // The platform settles into a loop, so skip ahead once a layout repeats
+        let mut seen: HashMap<Grid<char>, usize> = HashMap::new();
+        for i in 0..CYCLES {
+            if let Some(previous) = seen.insert(platform.clone(), i) {
+                for _ in 0..(CYCLES - i) % (i - previous) {
+                    cycle(&mut platform);
                 }
+                break;
             }
-        }
-
-        count
-    }
 
-    pub fn run() {
-        let contents = fs::read_to_string("inputs/day14.txt").expect("File not found");
-
-        let result = calculate_cycle_load(&contents);
+            cycle(&mut platform);
+        }
 
-        println!("Day 14 Part 2: {}", result);
+        north_load(&platform)
     }
 
     #[cfg(test)]
@@ -178,7 +108,7 @@ pub mod part2 {
 
         #[test]
         fn test_day14_part2() {
-            assert_eq!(calculate_cycle_load("O....#....\nO.OO#....#\n.....##...\nOO.#O....O\n.O.....O#.\nO.#..O.#.#\n..O..#O..O\n.......O..\n#....###..\n#OO..#...."), 64);
+            assert_eq!(calculate_cycle_load(&Day14::parse("O....#....\nO.OO#....#\n.....##...\nOO.#O....O\n.O.....O#.\nO.#..O.#.#\n..O..#O..O\n.......O..\n#....###..\n#OO..#....").unwrap()), 64);
         }
     }
 }
@@ -1,4 +1,6 @@
-fn hash(string: &str) -> usize {
+use crate::{error::ParseError, solution::Solution};
+
+pub fn hash(string: &str) -> usize {
     let mut result = 0;
 
     for char in string.chars() {
@@ -10,26 +12,83 @@ fn hash(string: &str) -> usize {
     result
 }
 
-pub mod part1 {
-    use super::*;
-    use std::fs;
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Hash)]
+pub enum Action {
+    Insert,
+    Remove,
+}
 
-    fn hash_sum(contents: &str) -> usize {
-        let mut sum = 0;
+pub type BoxEntry = (String, usize, Action, usize);
+
+// A step as written, which part 1 hashes, and the box entry it describes for part 2
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Step {
+    pub text: String,
+    pub entry: BoxEntry,
+}
 
-        for string in contents.split(',') {
-            sum += hash(string);
+pub fn parse_step(string: &str) -> Result<BoxEntry, ParseError> {
+    let (label, action, focal_length) = match string.strip_suffix('-') {
+        Some(label) => (label, Action::Remove, 0),
+        None => {
+            let (label, focal_length) = string.split_once('=').ok_or_else(|| {
+                ParseError::new(
+                    string,
+                    "expected `<label>=<focal length>` or `<label>-`, found",
+                )
+            })?;
+            let focal_length = focal_length
+                .parse::<usize>()
+                .map_err(|_| ParseError::new(string, "expected a focal length in"))?;
+
+            (label, Action::Insert, focal_length)
         }
+    };
 
-        sum
+    Ok((label.to_string(), hash(label), action, focal_length))
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Input = Vec<Step>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(contents: &str) -> Result<Self::Input, ParseError> {
+        let mut steps = Vec::new();
+        let mut column = contents.chars().count() - contents.trim_start().chars().count() + 1;
+        for text in contents.trim().split(',') {
+            steps.push(Step {
+                text: text.to_string(),
+                entry: parse_step(text).map_err(|error| error.at(1, column))?,
+            });
+            column += text.chars().count() + 1;
+        }
+
+        Ok(steps)
     }
 
-    pub fn run() {
-        let contents = fs::read_to_string("inputs/day15.txt").expect("File not found");
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1::hash_sum(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2::calculate_focusing_power(input)
+    }
+}
+
+pub mod part1 {
+    use super::*;
+
+    pub fn hash_sum(steps: &[Step]) -> usize {
+        let mut sum = 0;
 
-        let result = hash_sum(&contents);
+        for step in steps {
+            sum += hash(&step.text);
+        }
 
-        println!("Day 15 Part 1: {}", result);
+        sum
     }
 
     #[cfg(test)]
@@ -38,13 +97,15 @@ pub mod part1 {
 
         #[test]
         fn test_day15_part1a() {
-            assert_eq!(hash_sum("HASH"), 52);
+            assert_eq!(hash("HASH"), 52);
         }
 
         #[test]
         fn test_day15_part1b() {
             assert_eq!(
-                hash_sum("rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7"),
+                hash_sum(
+                    &Day15::parse("rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7").unwrap()
+                ),
                 1320
             );
         }
@@ -52,44 +113,18 @@ pub mod part1 {
 }
 
 pub mod part2 {
-    use regex::Regex;
-
     use super::*;
-    use std::fs;
 
-    #[derive(Debug, Eq, PartialEq, Clone, Copy, Hash)]
-    enum Action {
-        Insert,
-        Remove,
-    }
-
-    type BoxEntry<'a> = (&'a str, usize, Action, usize);
-
-    fn parse(string: &str) -> BoxEntry {
-        let re = Regex::new(r"^(.*)([=\-])([0-9]*)$").unwrap();
-        let (_, [p1, p2, p3]) = re.captures(string).expect("Failed to parse").extract();
-        let action = if p2 == "=" {
-            Action::Insert
-        } else if p2 == "-" {
-            Action::Remove
-        } else {
-            panic!("Failed to parse action")
-        };
-
-        (p1, hash(p1), action, p3.parse::<usize>().unwrap_or(0))
-    }
-
-    fn calculate_focusing_power(contents: &str) -> usize {
-        let mut boxes: Vec<Vec<BoxEntry>> = vec![vec![]; 256];
-
-        for string in contents.split(',') {
-            let entry = parse(string);
+    pub fn calculate_focusing_power(steps: &[Step]) -> usize {
+        let mut boxes: Vec<Vec<&BoxEntry>> = vec![vec![]; 256];
 
+        for Step { entry, .. } in steps {
             let (label, box_idx, action, _) = entry;
+            let (box_idx, action) = (*box_idx, *action);
 
             if let Some(other_idx) = boxes[box_idx]
                 .iter()
-                .position(|(other_label, _, _, _)| *other_label == label)
+                .position(|(other_label, _, _, _)| other_label == label)
             {
                 if action == Action::Insert {
                     boxes[box_idx][other_idx] = entry;
@@ -112,14 +147,6 @@ pub mod part2 {
         result
     }
 
-    pub fn run() {
-        let contents = fs::read_to_string("inputs/day15.txt").expect("File not found");
-
-        let result = calculate_focusing_power(&contents);
-
-        println!("Day 15 Part 2: {}", result);
-    }
-
     #[cfg(test)]
     mod tests {
         use super::*;
@@ -131,9 +158,32 @@ pub mod part2 {
         #[test]
         fn test_day15_part2b() {
             assert_eq!(
-                calculate_focusing_power("rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7"),
+                calculate_focusing_power(
+                    &Day15::parse("rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7").unwrap()
+                ),
                 145
             );
         }
+
+        #[test]
+        fn test_day15_parse_error() {
+            let error = Day15::parse("rn=1,cm+2,qp=3").unwrap_err();
+
+            assert_eq!(error.text, "cm+2");
+
+            for step in ["rn=", "rn=x", "rn=-1"] {
+                assert_eq!(Day15::parse(step).unwrap_err().text, step);
+            }
+        }
+
+        #[test]
+        fn test_day15_parse_error_column() {
+            let error = Day15::parse("\u{3000}rn=1,äb=2,cm=").unwrap_err();
+
+            assert_eq!(
+                (error.line, error.column, error.text.as_str()),
+                (1, 12, "cm=")
+            );
+        }
     }
 }
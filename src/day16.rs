@@ -1,435 +1,151 @@
-use std::{collections::HashMap, fs};
-
-#[derive(Debug, Eq, PartialEq, Clone, Copy, Hash)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-fn traverse_matrix(
-    matrix: &Vec<Vec<&str>>,
-    visited: &mut HashMap<(usize, usize), usize>,
-    visited_from_direction: &mut HashMap<(usize, usize, Direction), bool>,
-    x: usize,
-    y: usize,
-    direction: Direction,
-) {
-    if x > matrix.len() - 1 || y > matrix[x].len() - 1 {
-        return;
-    }
-
-    visited.entry((x, y)).and_modify(|c| *c = *c + 1);
-
-    if *visited_from_direction
-        .get(&(x, y, direction))
-        .unwrap_or(&false)
-    {
-        return;
+use crate::{
+    error::ParseError,
+    geometry::{Direction, Point},
+    grid::Grid,
+    solution::Solution,
+};
+
+// Directions a beam leaves a tile in after entering it while travelling in `direction`
+pub fn deflect(tile: char, direction: Direction) -> Vec<Direction> {
+    let horizontal = matches!(direction, Direction::Left | Direction::Right);
+
+    match (tile, horizontal) {
+        ('|', true) | ('-', false) => vec![direction.turn_left(), direction.turn_right()],
+        ('/', true) | ('\\', false) => vec![direction.turn_left()],
+        ('/', false) | ('\\', true) => vec![direction.turn_right()],
+        _ => vec![direction],
     }
+}
 
-    visited_from_direction.insert((x, y, direction), true);
-
-    if direction == Direction::Left {
-        if y > 0 && (matrix[x][y] == "." || matrix[x][y] == "-") {
-            traverse_matrix(matrix, visited, visited_from_direction, x, y - 1, direction);
-        }
+// Follows the beam entering at `start`, counting every tile it passes through. A beam that
+// reaches a tile it already crossed in the same direction repeats an earlier path, so it stops.
+pub fn energize(contraption: &Grid<char>, start: Point, direction: Direction) -> usize {
+    let mut seen = Grid::filled(contraption.width(), contraption.height(), [false; 4]);
+    let mut beams = vec![(start, direction)];
 
-        if matrix[x][y] == "|" {
-            if x > 0 {
-                traverse_matrix(
-                    matrix,
-                    visited,
-                    visited_from_direction,
-                    x - 1,
-                    y,
-                    Direction::Up,
-                );
-            }
-            traverse_matrix(
-                matrix,
-                visited,
-                visited_from_direction,
-                x + 1,
-                y,
-                Direction::Down,
-            );
+    while let Some((position, direction)) = beams.pop() {
+        let crossed = &mut seen[position][direction as usize];
+        if *crossed {
+            continue;
         }
+        *crossed = true;
 
-        if matrix[x][y] == "\\" {
-            if x > 0 {
-                traverse_matrix(
-                    matrix,
-                    visited,
-                    visited_from_direction,
-                    x - 1,
-                    y,
-                    Direction::Up,
-                );
+        for next in deflect(contraption[position], direction) {
+            if let Some(next_position) = contraption.step(position, next) {
+                beams.push((next_position, next));
             }
         }
-
-        if matrix[x][y] == "/" {
-            traverse_matrix(
-                matrix,
-                visited,
-                visited_from_direction,
-                x + 1,
-                y,
-                Direction::Down,
-            );
-        }
     }
 
-    if direction == Direction::Right {
-        if y < matrix[x].len() - 1 && (matrix[x][y] == "." || matrix[x][y] == "-") {
-            traverse_matrix(matrix, visited, visited_from_direction, x, y + 1, direction);
-        }
+    seen.iter()
+        .filter(|(_, directions)| directions.contains(&true))
+        .count()
+}
 
-        if matrix[x][y] == "|" {
-            if x > 0 {
-                traverse_matrix(
-                    matrix,
-                    visited,
-                    visited_from_direction,
-                    x - 1,
-                    y,
-                    Direction::Up,
-                );
-            }
-            traverse_matrix(
-                matrix,
-                visited,
-                visited_from_direction,
-                x + 1,
-                y,
-                Direction::Down,
-            );
-        }
+pub struct Day16;
 
-        if matrix[x][y] == "\\" {
-            traverse_matrix(
-                matrix,
-                visited,
-                visited_from_direction,
-                x + 1,
-                y,
-                Direction::Down,
-            );
-        }
+impl Solution for Day16 {
+    type Input = Grid<char>;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-        if matrix[x][y] == "/" {
-            if x > 0 {
-                traverse_matrix(
-                    matrix,
-                    visited,
-                    visited_from_direction,
-                    x - 1,
-                    y,
-                    Direction::Up,
-                );
-            }
-        }
-    }
+    // Part 2 enters from every edge, so the contraption needs at least one tile
+    fn parse(contents: &str) -> Result<Self::Input, ParseError> {
+        let contraption = Grid::parse(contents, |c| {
+            matches!(c, '.' | '/' | '\\' | '|' | '-').then_some(c)
+        })?;
 
-    if direction == Direction::Up {
-        if x > 0 && (matrix[x][y] == "." || matrix[x][y] == "|") {
-            traverse_matrix(matrix, visited, visited_from_direction, x - 1, y, direction);
+        if contraption.is_empty() {
+            return Err(ParseError::new(
+                contents,
+                "expected at least one tile, found",
+            ));
         }
 
-        if matrix[x][y] == "-" {
-            if y > 0 {
-                traverse_matrix(
-                    matrix,
-                    visited,
-                    visited_from_direction,
-                    x,
-                    y - 1,
-                    Direction::Left,
-                );
-            }
-            traverse_matrix(
-                matrix,
-                visited,
-                visited_from_direction,
-                x,
-                y + 1,
-                Direction::Right,
-            );
-        }
-
-        if matrix[x][y] == "\\" {
-            if y > 0 {
-                traverse_matrix(
-                    matrix,
-                    visited,
-                    visited_from_direction,
-                    x,
-                    y - 1,
-                    Direction::Left,
-                );
-            }
-        }
-
-        if matrix[x][y] == "/" {
-            traverse_matrix(
-                matrix,
-                visited,
-                visited_from_direction,
-                x,
-                y + 1,
-                Direction::Right,
-            );
-        }
+        Ok(contraption)
     }
 
-    if direction == Direction::Down {
-        if x < matrix.len() - 1 && (matrix[x][y] == "." || matrix[x][y] == "|") {
-            traverse_matrix(matrix, visited, visited_from_direction, x + 1, y, direction);
-        }
-
-        if matrix[x][y] == "-" {
-            if y > 0 {
-                traverse_matrix(
-                    matrix,
-                    visited,
-                    visited_from_direction,
-                    x,
-                    y - 1,
-                    Direction::Left,
-                );
-            }
-            traverse_matrix(
-                matrix,
-                visited,
-                visited_from_direction,
-                x,
-                y + 1,
-                Direction::Right,
-            );
-        }
-
-        if matrix[x][y] == "\\" {
-            traverse_matrix(
-                matrix,
-                visited,
-                visited_from_direction,
-                x,
-                y + 1,
-                Direction::Right,
-            );
-        }
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1::count_energized_tiles(input)
+    }
 
-        if matrix[x][y] == "/" {
-            if y > 0 {
-                traverse_matrix(
-                    matrix,
-                    visited,
-                    visited_from_direction,
-                    x,
-                    y - 1,
-                    Direction::Left,
-                );
-            }
-        }
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2::count_energized_tiles_multiple_entrypoints(input)
     }
 }
 
 pub mod part1 {
     use super::*;
 
-    fn count_energized_tiles(contents: &str) -> usize {
-        let matrix: Vec<Vec<&str>> = contents
-            .lines()
-            .map(|line| {
-                line.split("")
-                    .filter(|line: &&str| !line.is_empty())
-                    .collect::<Vec<&str>>()
-            })
-            .collect();
-
-        let mut visited: HashMap<(usize, usize), usize> = HashMap::new();
-        for (x, row) in matrix.iter().enumerate() {
-            for (y, _) in row.iter().enumerate() {
-                visited.insert((x, y), 0);
-            }
-        }
-
-        let mut visited_from_direction: HashMap<(usize, usize, Direction), bool> = HashMap::new();
-
-        traverse_matrix(
-            &matrix,
-            &mut visited,
-            &mut visited_from_direction,
-            0,
-            0,
-            Direction::Right,
-        );
-
-        visited.iter().filter(|(_, &count)| count > 0).count()
-    }
-
-    pub fn run() {
-        let contents = fs::read_to_string("inputs/day16.txt").expect("File not found");
-
-        let result = count_energized_tiles(&contents);
-
-        println!("Day 16 Part 1: {}", result);
+    pub fn count_energized_tiles(contraption: &Grid<char>) -> usize {
+        energize(contraption, Point::new(0, 0), Direction::Right)
     }
 
     #[cfg(test)]
     mod tests {
         use super::*;
+        use std::fs;
 
         #[test]
         fn test_day16_part1() {
             assert_eq!(
                 count_energized_tiles(
-                    &fs::read_to_string("inputs/day16-example.txt").expect("File not found")
+                    &Day16::parse(
+                        &fs::read_to_string("inputs/day16-example.txt").expect("File not found")
+                    )
+                    .unwrap()
                 ),
                 46
             );
         }
+
+        #[test]
+        fn test_day16_empty() {
+            assert!(Day16::parse("").is_err());
+            assert!(Day16::parse("\n").is_err());
+            assert_eq!(count_energized_tiles(&Day16::parse(".").unwrap()), 1);
+        }
     }
 }
 
 pub mod part2 {
     use super::*;
-    use std::fs;
-
-    fn count_energized_tiles_multiple_entrypoints(contents: &str) -> usize {
-        let matrix: Vec<Vec<&str>> = contents
-            .lines()
-            .map(|line| {
-                line.split("")
-                    .filter(|line: &&str| !line.is_empty())
-                    .collect::<Vec<&str>>()
-            })
-            .collect();
-
-        let mut configurations: HashMap<(usize, usize), usize> = HashMap::new();
-
-        for x in 0..matrix.len() {
-            let mut visited1: HashMap<(usize, usize), usize> = HashMap::new();
-            for (x, row) in matrix.iter().enumerate() {
-                for (y, _) in row.iter().enumerate() {
-                    visited1.insert((x, y), 0);
-                }
-            }
-
-            let mut visited2: HashMap<(usize, usize), usize> = HashMap::new();
-            for (x, row) in matrix.iter().enumerate() {
-                for (y, _) in row.iter().enumerate() {
-                    visited1.insert((x, y), 0);
-                }
-            }
-
-            let mut visited_from_direction1: HashMap<(usize, usize, Direction), bool> =
-                HashMap::new();
-
-            traverse_matrix(
-                &matrix,
-                &mut visited1,
-                &mut visited_from_direction1,
-                x,
-                0,
-                Direction::Right,
-            );
-
-            configurations.insert(
-                (x, 0),
-                visited1.iter().filter(|(_, &count)| count > 0).count(),
-            );
-
-            let mut visited_from_direction2: HashMap<(usize, usize, Direction), bool> =
-                HashMap::new();
-
-            traverse_matrix(
-                &matrix,
-                &mut visited2,
-                &mut visited_from_direction2,
-                x,
-                matrix[x].len() - 1,
-                Direction::Left,
-            );
-
-            configurations.insert(
-                (x, matrix[x].len() - 1),
-                visited2.iter().filter(|(_, &count)| count > 0).count(),
-            );
-        }
-
-        for y in 0..matrix[0].len() {
-            let mut visited1: HashMap<(usize, usize), usize> = HashMap::new();
-            for (x, row) in matrix.iter().enumerate() {
-                for (y, _) in row.iter().enumerate() {
-                    visited1.insert((x, y), 0);
-                }
-            }
-
-            let mut visited2: HashMap<(usize, usize), usize> = HashMap::new();
-            for (x, row) in matrix.iter().enumerate() {
-                for (y, _) in row.iter().enumerate() {
-                    visited1.insert((x, y), 0);
-                }
-            }
-
-            let mut visited_from_direction1: HashMap<(usize, usize, Direction), bool> =
-                HashMap::new();
-
-            traverse_matrix(
-                &matrix,
-                &mut visited1,
-                &mut visited_from_direction1,
-                0,
-                y,
-                Direction::Down,
-            );
-
-            configurations.insert(
-                (0, y),
-                visited1.iter().filter(|(_, &count)| count > 0).count(),
-            );
-
-            let mut visited_from_direction2: HashMap<(usize, usize, Direction), bool> =
-                HashMap::new();
-
-            traverse_matrix(
-                &matrix,
-                &mut visited2,
-                &mut visited_from_direction2,
-                matrix.len() - 1,
-                y,
-                Direction::Up,
-            );
-
-            configurations.insert(
-                (matrix.len() - 1, y),
-                visited2.iter().filter(|(_, &count)| count > 0).count(),
-            );
-        }
-
-        *configurations.values().max().unwrap()
-    }
-
-    pub fn run() {
-        let contents = fs::read_to_string("inputs/day16.txt").expect("File not found");
-
-        let result = count_energized_tiles_multiple_entrypoints(&contents);
 
-        println!("Day 16 Part 2: {}", result);
+    pub fn count_energized_tiles_multiple_entrypoints(contraption: &Grid<char>) -> usize {
+        let (last_row, last_column) = (contraption.height() - 1, contraption.width() - 1);
+
+        let rows = (0..contraption.height()).flat_map(|x| {
+            [
+                (Point::new(x, 0), Direction::Right),
+                (Point::new(x, last_column), Direction::Left),
+            ]
+        });
+        let columns = (0..contraption.width()).flat_map(|y| {
+            [
+                (Point::new(0, y), Direction::Down),
+                (Point::new(last_row, y), Direction::Up),
+            ]
+        });
+
+        rows.chain(columns)
+            .map(|(start, direction)| energize(contraption, start, direction))
+            .max()
+            .unwrap()
     }
 
     #[cfg(test)]
     mod tests {
         use super::*;
+        use std::fs;
 
         #[test]
         fn test_day16_part2() {
             assert_eq!(
                 count_energized_tiles_multiple_entrypoints(
-                    &fs::read_to_string("inputs/day16-example.txt").expect("File not found")
+                    &Day16::parse(
+                        &fs::read_to_string("inputs/day16-example.txt").expect("File not found")
+                    )
+                    .unwrap()
                 ),
                 51
             );
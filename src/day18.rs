@@ -0,0 +1,197 @@
+use crate::{
+    error::{number, parse_lines, ParseError},
+    solution::Solution,
+};
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    pub fn parse(value: &str) -> Result<Self, ParseError> {
+        match value {
+            "U" | "3" => Ok(Direction::Up),
+            "D" | "1" => Ok(Direction::Down),
+            "L" | "2" => Ok(Direction::Left),
+            "R" | "0" => Ok(Direction::Right),
+            _ => Err(ParseError::new(value, "expected a direction, found")),
+        }
+    }
+
+    pub fn delta(&self) -> (i64, i64) {
+        match self {
+            Direction::Up => (-1, 0),
+            Direction::Down => (1, 0),
+            Direction::Left => (0, -1),
+            Direction::Right => (0, 1),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Instruction {
+    pub direction: Direction,
+    pub length: i64,
+}
+
+impl Instruction {
+    pub fn parse(line: &str) -> Result<Self, ParseError> {
+        let [direction, length, _] = fields(line)?;
+
+        Ok(Self {
+            direction: Direction::parse(direction)?,
+            length: number(length)?,
+        })
+    }
+
+    pub fn parse_color(line: &str) -> Result<Self, ParseError> {
+        let [_, _, color] = fields(line)?;
+        let hex = color
+            .strip_prefix("(#")
+            .and_then(|color| color.strip_suffix(')'))
+            .filter(|hex| hex.len() == 6 && hex.is_ascii())
+            .ok_or_else(|| ParseError::new(color, "expected `(#rrggbb)`, found"))?;
+        let (length, direction) = hex.split_at(5);
+
+        Ok(Self {
+            direction: Direction::parse(direction)?,
+            length: i64::from_str_radix(length, 16)
+                .map_err(|_| ParseError::new(length, "expected a hexadecimal length, found"))?,
+        })
+    }
+}
+
+// Splits a `<direction> <length> (#<color>)` line into its three fields
+fn fields(line: &str) -> Result<[&str; 3], ParseError> {
+    let parts: Vec<&str> = line.split_whitespace().collect();
+
+    parts
+        .try_into()
+        .map_err(|_| ParseError::new(line, "expected `<direction> <length> (#<color>)`, found"))
+}
+
+// Both readings of the dig plan: the plain instructions and those hidden in the colours
+#[derive(Debug)]
+pub struct DigPlan {
+    pub instructions: Vec<Instruction>,
+    pub color_instructions: Vec<Instruction>,
+}
+
+impl DigPlan {
+    pub fn parse(contents: &str) -> Result<Self, ParseError> {
+        Ok(Self {
+            instructions: parse_lines(contents, Instruction::parse)?,
+            color_instructions: parse_lines(contents, Instruction::parse_color)?,
+        })
+    }
+}
+
+pub fn lagoon_area(instructions: &[Instruction]) -> i64 {
+    let (mut x, mut y) = (0, 0);
+    let mut area = 0;
+    let mut perimeter = 0;
+
+    for instruction in instructions {
+        let (dx, dy) = instruction.direction.delta();
+        let nx = x + dx * instruction.length;
+        let ny = y + dy * instruction.length;
+
+        // Shoelace formula for the area enclosed by the centre of the trench
+        area += x * ny - nx * y;
+        perimeter += instruction.length;
+
+        x = nx;
+        y = ny;
+    }
+
+    // Pick's theorem gives the interior points, the trench itself adds the boundary
+    area.abs() / 2 + perimeter / 2 + 1
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Input = DigPlan;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(contents: &str) -> Result<Self::Input, ParseError> {
+        DigPlan::parse(contents)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1::cubic_meters(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2::cubic_meters(input)
+    }
+}
+
+pub mod part1 {
+    use super::*;
+
+    pub fn cubic_meters(plan: &DigPlan) -> i64 {
+        lagoon_area(&plan.instructions)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_day18_part1() {
+            assert_eq!(cubic_meters(&Day18::parse("R 6 (#70c710)\nD 5 (#0dc571)\nL 2 (#5713f0)\nD 2 (#d2c081)\nR 2 (#59c680)\nD 2 (#411b91)\nL 5 (#8ceee2)\nU 2 (#caa173)\nL 1 (#1b58a2)\nU 2 (#caa171)\nR 2 (#7807d2)\nU 3 (#a77fa3)\nL 2 (#015232)\nU 2 (#7a21e3)").unwrap()), 62);
+        }
+
+        #[test]
+        fn test_day18_part1_square() {
+            assert_eq!(
+                cubic_meters(
+                    &Day18::parse("R 2 (#000000)\nD 2 (#000000)\nL 2 (#000000)\nU 2 (#000000)")
+                        .unwrap()
+                ),
+                9
+            );
+        }
+    }
+}
+
+pub mod part2 {
+    use super::*;
+
+    pub fn cubic_meters(plan: &DigPlan) -> i64 {
+        lagoon_area(&plan.color_instructions)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_day18_part2() {
+            assert_eq!(cubic_meters(&Day18::parse("R 6 (#70c710)\nD 5 (#0dc571)\nL 2 (#5713f0)\nD 2 (#d2c081)\nR 2 (#59c680)\nD 2 (#411b91)\nL 5 (#8ceee2)\nU 2 (#caa173)\nL 1 (#1b58a2)\nU 2 (#caa171)\nR 2 (#7807d2)\nU 3 (#a77fa3)\nL 2 (#015232)\nU 2 (#7a21e3)").unwrap()), 952408144115);
+        }
+
+        #[test]
+        fn test_day18_part2_instruction() {
+            let instruction = Instruction::parse_color("R 6 (#70c710)").unwrap();
+            assert_eq!(instruction.direction, Direction::Right);
+            assert_eq!(instruction.length, 461937);
+        }
+
+        #[test]
+        fn test_day18_parse_error() {
+            let error = Day18::parse("R 6 (#70c710)\nD 5 (#0dc575)").unwrap_err();
+
+            assert_eq!(
+                (error.text.as_str(), error.message.as_str()),
+                ("5", "expected a direction, found")
+            );
+        }
+    }
+}
@@ -0,0 +1,313 @@
+use crate::{
+    error::{number, parse_lines, split_once, ParseError},
+    solution::Solution,
+};
+use std::collections::HashMap;
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum Comparison {
+    LessThan,
+    GreaterThan,
+}
+
+#[derive(Debug)]
+pub struct Rule {
+    pub condition: Option<(usize, Comparison, u64)>,
+    pub target: String,
+}
+
+impl Rule {
+    pub fn parse(rule: &str) -> Result<Self, ParseError> {
+        match rule.split_once(':') {
+            Some((condition, target)) => {
+                if condition.len() < 3 || !condition.is_char_boundary(2) {
+                    return Err(ParseError::new(condition, "expected a condition, found"));
+                }
+
+                let category = category_index(&condition[0..1])?;
+                let comparison = match &condition[1..2] {
+                    "<" => Comparison::LessThan,
+                    ">" => Comparison::GreaterThan,
+                    value => return Err(ParseError::new(value, "expected `<` or `>`, found")),
+                };
+                let value = number(&condition[2..])?;
+
+                Ok(Self {
+                    condition: Some((category, comparison, value)),
+                    target: target.to_string(),
+                })
+            }
+            None => Ok(Self {
+                condition: None,
+                target: rule.to_string(),
+            }),
+        }
+    }
+
+    pub fn matches(&self, part: &Part) -> bool {
+        match self.condition {
+            Some((category, Comparison::LessThan, value)) => part.ratings[category] < value,
+            Some((category, Comparison::GreaterThan, value)) => part.ratings[category] > value,
+            None => true,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Workflow {
+    pub name: String,
+    pub rules: Vec<Rule>,
+}
+
+impl Workflow {
+    // The last rule must be a plain target so that every part leaves the workflow somewhere
+    pub fn parse(line: &str) -> Result<Self, ParseError> {
+        let (name, rules_str) = split_once(line.trim_end_matches('}'), "{")?;
+        let rules: Vec<Rule> = rules_str
+            .split(',')
+            .map(Rule::parse)
+            .collect::<Result<_, _>>()?;
+
+        match rules.last() {
+            Some(Rule {
+                condition: None,
+                target,
+            }) if !target.is_empty() => {}
+            _ => {
+                return Err(ParseError::new(
+                    rules_str,
+                    "expected the rules to end with a plain target, found",
+                ))
+            }
+        }
+
+        Ok(Self {
+            name: name.to_string(),
+            rules,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct Part {
+    pub ratings: [u64; 4],
+}
+
+impl Part {
+    pub fn parse(line: &str) -> Result<Self, ParseError> {
+        let mut ratings = [0; 4];
+
+        for rating in line.trim_matches(|c| c == '{' || c == '}').split(',') {
+            let (category, value) = split_once(rating, "=")?;
+            ratings[category_index(category)?] = number(value)?;
+        }
+
+        Ok(Self { ratings })
+    }
+}
+
+pub fn category_index(category: &str) -> Result<usize, ParseError> {
+    match category {
+        "x" => Ok(0),
+        "m" => Ok(1),
+        "a" => Ok(2),
+        "s" => Ok(3),
+        _ => Err(ParseError::new(
+            category,
+            "expected `x`, `m`, `a` or `s`, found",
+        )),
+    }
+}
+
+#[derive(Debug)]
+pub struct System {
+    pub workflows: HashMap<String, Workflow>,
+    pub parts: Vec<Part>,
+}
+
+impl System {
+    pub fn parse(contents: &str) -> Result<Self, ParseError> {
+        let (workflows_str, parts_str) = split_once(contents, "\n\n")?;
+        let workflows: HashMap<String, Workflow> = parse_lines(workflows_str, |line| {
+            Workflow::parse(line).map(|workflow| (workflow.name.clone(), workflow))
+        })?
+        .into_iter()
+        .collect();
+
+        if !workflows.contains_key("in") {
+            return Err(ParseError::new(workflows_str, "missing workflow `in` in"));
+        }
+
+        // Every rule has to send parts somewhere, otherwise sorting them would get stuck
+        for (index, line) in workflows_str.lines().enumerate() {
+            for rule in line.split(['{', ',', '}']) {
+                let target = rule.rsplit(':').next().unwrap_or(rule);
+                if !target.is_empty()
+                    && !matches!(target, "A" | "R")
+                    && !workflows.contains_key(target)
+                {
+                    return Err(ParseError::new(target, "unknown workflow").on_line(index, line));
+                }
+            }
+        }
+
+        // The parts start after the workflows and the blank line
+        let offset = workflows_str.matches('\n').count() + 2;
+
+        Ok(Self {
+            workflows,
+            parts: parse_lines(parts_str, Part::parse).map_err(|error| error.below(offset))?,
+        })
+    }
+
+    pub fn is_accepted(&self, part: &Part) -> bool {
+        let mut name = "in";
+
+        loop {
+            match name {
+                "A" => return true,
+                "R" => return false,
+                _ => {}
+            }
+
+            let workflow = &self.workflows[name];
+            name = &workflow
+                .rules
+                .iter()
+                .find(|rule| rule.matches(part))
+                .expect("Workflows end with a plain target, checked by Workflow::parse")
+                .target;
+        }
+    }
+
+    // Each range is an inclusive (min, max) pair per category
+    pub fn count_accepted(&self, name: &str, mut ranges: [(u64, u64); 4]) -> u64 {
+        match name {
+            "A" => return ranges.iter().map(|(min, max)| max - min + 1).product(),
+            "R" => return 0,
+            _ => {}
+        }
+
+        let mut count = 0;
+        let workflow = &self.workflows[name];
+
+        for rule in &workflow.rules {
+            match rule.condition {
+                Some((category, comparison, value)) => {
+                    let (min, max) = ranges[category];
+                    let (matching, remaining) = match comparison {
+                        Comparison::LessThan => (
+                            (min, max.min(value.saturating_sub(1))),
+                            (min.max(value), max),
+                        ),
+                        Comparison::GreaterThan => {
+                            ((min.max(value + 1), max), (min, max.min(value)))
+                        }
+                    };
+
+                    if matching.0 <= matching.1 {
+                        let mut next_ranges = ranges;
+                        next_ranges[category] = matching;
+                        count += self.count_accepted(&rule.target, next_ranges);
+                    }
+
+                    if remaining.0 > remaining.1 {
+                        return count;
+                    }
+
+                    ranges[category] = remaining;
+                }
+                None => {
+                    count += self.count_accepted(&rule.target, ranges);
+                }
+            }
+        }
+
+        count
+    }
+}
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Input = System;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(contents: &str) -> Result<Self::Input, ParseError> {
+        System::parse(contents)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1::accepted_ratings(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2::accepted_combinations(input)
+    }
+}
+
+pub mod part1 {
+    use super::*;
+
+    pub fn accepted_ratings(system: &System) -> u64 {
+        system
+            .parts
+            .iter()
+            .filter(|part| system.is_accepted(part))
+            .map(|part| part.ratings.iter().sum::<u64>())
+            .sum()
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_day19_part1() {
+            assert_eq!(accepted_ratings(&Day19::parse("px{a<2006:qkq,m>2090:A,rfg}\npv{a>1716:R,A}\nlnx{m>1548:A,A}\nrfg{s<537:gd,x>2440:R,A}\nqs{s>3448:A,lnx}\nqkq{x<1416:A,crn}\ncrn{x>2662:A,R}\nin{s<1351:px,qqz}\nqqz{s>2770:qs,m<1801:hdj,R}\ngd{a>3333:R,R}\nhdj{m>838:A,pv}\n\n{x=787,m=2655,a=1222,s=2876}\n{x=1679,m=44,a=2067,s=496}\n{x=2036,m=264,a=79,s=2244}\n{x=2461,m=1339,a=466,s=291}\n{x=2127,m=1623,a=2188,s=1013}").unwrap()), 19114);
+        }
+    }
+}
+
+pub mod part2 {
+    use super::*;
+
+    pub fn accepted_combinations(system: &System) -> u64 {
+        system.count_accepted("in", [(1, 4000); 4])
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_day19_part2() {
+            assert_eq!(accepted_combinations(&Day19::parse("px{a<2006:qkq,m>2090:A,rfg}\npv{a>1716:R,A}\nlnx{m>1548:A,A}\nrfg{s<537:gd,x>2440:R,A}\nqs{s>3448:A,lnx}\nqkq{x<1416:A,crn}\ncrn{x>2662:A,R}\nin{s<1351:px,qqz}\nqqz{s>2770:qs,m<1801:hdj,R}\ngd{a>3333:R,R}\nhdj{m>838:A,pv}\n\n{x=787,m=2655,a=1222,s=2876}\n{x=1679,m=44,a=2067,s=496}\n{x=2036,m=264,a=79,s=2244}\n{x=2461,m=1339,a=466,s=291}\n{x=2127,m=1623,a=2188,s=1013}").unwrap()), 167409079868000);
+        }
+
+        #[test]
+        fn test_day19_part2_accept_all() {
+            assert_eq!(
+                accepted_combinations(&Day19::parse("in{A}\n\n{x=1,m=1,a=1,s=1}").unwrap()),
+                4000u64.pow(4)
+            );
+        }
+
+        #[test]
+        fn test_day19_parse_errors() {
+            let error = Day19::parse("in{x<10:zz,A}\n\n{x=1,m=1,a=1,s=1}").unwrap_err();
+            assert_eq!(
+                (error.message.as_str(), error.text.as_str()),
+                ("unknown workflow", "zz")
+            );
+
+            let error = Day19::parse("in{A}\n\n{x=1,q=1}").unwrap_err();
+            assert_eq!(error.text, "q");
+
+            let error = Day19::parse("in{A}\nab{x<10:A}\n\n{x=1,m=1,a=1,s=1}").unwrap_err();
+            assert_eq!((error.line, error.text.as_str()), (2, "x<10:A"));
+            assert!(Day19::parse("in{}\n\n{x=1,m=1,a=1,s=1}").is_err());
+        }
+    }
+}
@@ -1,163 +1,382 @@
+use crate::{
+    error::{number, parse_lines, split_once, LoadError, ParseError},
+    solution::Solution,
+};
 use regex::Regex;
-use std::fs;
+use std::{any::Any, collections::BTreeMap, fmt::Display, fs};
 
-const MAX_REDS: i32 = 12;
-const MAX_GREENS: i32 = 13;
-const MAX_BLUES: i32 = 14;
-
-struct Game {
-    id: i32,
-    reveals: Vec<Reveal>,
+// How many cubes of each colour the bag holds. Colours the bag does not mention have none.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Bag {
+    pub limits: BTreeMap<String, u32>,
 }
 
-impl Game {
-    fn is_legit(&self) -> bool {
-        for reveal in self.reveals.iter() {
-            if !reveal.is_legit() {
-                return false;
-            }
+impl Bag {
+    // The bag from the puzzle: 12 red, 13 green and 14 blue cubes
+    pub fn standard() -> Self {
+        Self {
+            limits: [("red", 12), ("green", 13), ("blue", 14)]
+                .into_iter()
+                .map(|(colour, count)| (colour.to_string(), count))
+                .collect(),
         }
-
-        true
     }
 
-    fn max_red(&self) -> i32 {
-        let mut max_red: i32 = 1;
+    // Reads `colour = count` entries separated by commas or newlines, e.g. `red=12,green=13`.
+    // Lines starting with `#` are comments.
+    pub fn parse(spec: &str) -> Result<Self, ParseError> {
+        let entry = |entry: &str| -> Result<(String, u32), ParseError> {
+            let (colour, count) = split_once(entry, "=")?;
+            Ok((colour.trim().to_string(), number(count)?))
+        };
+
+        let limits = spec
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim_start().starts_with('#'))
+            .flat_map(|(index, line)| line.split(',').map(move |entry| (index, line, entry)))
+            .filter(|(_, _, entry)| !entry.trim().is_empty())
+            .map(|(index, line, text)| entry(text).map_err(|error| error.on_line(index, line)))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { limits })
+    }
 
-        for reveal in self.reveals.iter() {
-            if reveal.red > max_red {
-                max_red = reveal.red;
-            }
+    // `value` is either an inline bag or a path to a bag file. An inline bag always has a `=`,
+    // so anything without one is read as a path.
+    pub fn load(value: &str) -> Result<Self, LoadError> {
+        if value.contains('=') {
+            return Bag::parse(value).map_err(|error| LoadError::Parse { path: None, error });
         }
 
-        max_red
+        let contents = fs::read_to_string(value).map_err(|error| LoadError::Read {
+            path: value.to_string(),
+            error,
+        })?;
+
+        Bag::parse(&contents).map_err(|error| LoadError::Parse {
+            path: Some(value.to_string()),
+            error,
+        })
     }
 
-    fn max_green(&self) -> i32 {
-        let mut max_green: i32 = 1;
+    pub fn limit(&self, colour: &str) -> u32 {
+        self.limits.get(colour).copied().unwrap_or(0)
+    }
 
-        for reveal in self.reveals.iter() {
-            if reveal.green > max_green {
-                max_green = reveal.green;
-            }
-        }
+    // Colours in the reveal that exceed the bag, with the count shown and the limit
+    pub fn violations<'a>(&self, reveal: &'a Reveal) -> Vec<(&'a str, u32, u32)> {
+        reveal
+            .cubes
+            .iter()
+            .filter(|(colour, &count)| count > self.limit(colour))
+            .map(|(colour, &count)| (colour.as_str(), count, self.limit(colour)))
+            .collect()
+    }
 
-        max_green
+    pub fn allows(&self, reveal: &Reveal) -> bool {
+        self.violations(reveal).is_empty()
     }
 
-    fn max_blue(&self) -> i32 {
-        let mut max_blue: i32 = 1;
+    // The product of every limit, widened so that bags with many colours do not overflow
+    pub fn power(&self) -> u64 {
+        self.limits.values().map(|&count| count as u64).product()
+    }
+}
 
-        for reveal in self.reveals.iter() {
-            if reveal.blue > max_blue {
-                max_blue = reveal.blue;
-            }
+impl Display for Bag {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let cubes: Vec<String> = self
+            .limits
+            .iter()
+            .map(|(colour, count)| format!("{} {}", count, colour))
+            .collect();
+
+        write!(f, "{}", cubes.join(", "))
+    }
+}
+
+pub struct Game {
+    pub id: i32,
+    pub reveals: Vec<Reveal>,
+}
+
+impl Game {
+    // The first reveal, counting from zero, that the bag could not have produced
+    pub fn first_violation(&self, bag: &Bag) -> Option<(usize, &Reveal)> {
+        self.reveals
+            .iter()
+            .enumerate()
+            .find(|(_, reveal)| !bag.allows(reveal))
+    }
+
+    pub fn is_legit(&self, bag: &Bag) -> bool {
+        self.first_violation(bag).is_none()
+    }
+
+    // The fewest cubes of each colour that make every reveal possible
+    pub fn minimal_bag(&self) -> Bag {
+        let mut limits: BTreeMap<String, u32> = BTreeMap::new();
+
+        for (colour, &count) in self.reveals.iter().flat_map(|reveal| &reveal.cubes) {
+            let limit = limits.entry(colour.clone()).or_insert(0);
+            *limit = (*limit).max(count);
         }
 
-        max_blue
+        Bag { limits }
     }
 
-    fn power(&self) -> i32 {
-        self.max_red() * self.max_green() * self.max_blue()
+    pub fn power(&self) -> u64 {
+        self.minimal_bag().power()
+    }
+
+    pub fn report(&self, bag: &Bag) -> String {
+        let verdict = match self.first_violation(bag) {
+            None => "legit".to_string(),
+            Some((index, reveal)) => {
+                let violations: Vec<String> = bag
+                    .violations(reveal)
+                    .iter()
+                    .map(|(colour, count, limit)| format!("{} {} > {}", count, colour, limit))
+                    .collect();
+
+                format!(
+                    "illegal, reveal {} ({}) shows {}",
+                    index + 1,
+                    reveal,
+                    violations.join(", ")
+                )
+            }
+        };
+
+        format!(
+            "Game {}: {}; minimal bag {}; power {}",
+            self.id,
+            verdict,
+            self.minimal_bag(),
+            self.power()
+        )
     }
 }
 
-struct Reveal {
-    red: i32,
-    green: i32,
-    blue: i32,
+// The cubes shown in one handful, by colour
+pub struct Reveal {
+    pub cubes: BTreeMap<String, u32>,
 }
 
-impl Reveal {
-    fn is_legit(&self) -> bool {
-        self.red <= MAX_REDS && self.green <= MAX_GREENS && self.blue <= MAX_BLUES
+impl Display for Reveal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let cubes: Vec<String> = self
+            .cubes
+            .iter()
+            .map(|(colour, count)| format!("{} {}", count, colour))
+            .collect();
+
+        write!(f, "{}", cubes.join(", "))
     }
 }
 
-fn parse_color(regex: &str, reveal_str: &str) -> i32 {
-    let re = Regex::new(regex).expect("failed to create color regex");
+pub fn create_reveal(reveal_str: &str) -> Result<Reveal, ParseError> {
+    let mut cubes: BTreeMap<String, u32> = BTreeMap::new();
+
+    for cube_str in reveal_str.split(',').filter(|cube| !cube.trim().is_empty()) {
+        let (count, colour) = cube_str.trim().split_once(' ').ok_or_else(|| {
+            ParseError::new(cube_str.trim(), "expected `<count> <colour>`, found")
+        })?;
 
-    match re.captures(reveal_str) {
-        Some(captures) => captures
-            .get(1)
-            .expect("Failed to get capture")
-            .as_str()
-            .parse::<i32>()
-            .unwrap(),
-        None => 0,
+        let total = cubes.entry(colour.trim().to_string()).or_insert(0);
+        *total = total
+            .checked_add(number(count)?)
+            .ok_or_else(|| ParseError::new(cube_str.trim(), "too many cubes in"))?;
     }
+
+    Ok(Reveal { cubes })
+}
+
+pub fn parse_games(contents: &str) -> Result<Vec<Game>, ParseError> {
+    let re = Regex::new(r"^Game ([0-9]+): (.*)$").unwrap();
+
+    parse_lines(contents, |line| {
+        let (_, [game_id, reveals_str]) = re
+            .captures(line)
+            .ok_or_else(|| ParseError::new(line, "expected `Game <id>: <reveals>`, found"))?
+            .extract();
+
+        let reveals = reveals_str
+            .split(';')
+            .map(create_reveal)
+            .collect::<Result<Vec<Reveal>, ParseError>>()?;
+
+        Ok(Game {
+            id: number(game_id)?,
+            reveals,
+        })
+    })
 }
 
-fn create_reveal(reveal_str: &str) -> Reveal {
-    Reveal {
-        red: parse_color(r"([0-9]+) red", reveal_str),
-        green: parse_color(r"([0-9]+) green", reveal_str),
-        blue: parse_color(r"([0-9]+) blue", reveal_str),
+// The games and the bag they are checked against, the standard one unless configured otherwise
+pub struct Games {
+    pub games: Vec<Game>,
+    pub bag: Bag,
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Games;
+    type Answer1 = i32;
+    type Answer2 = u64;
+
+    fn parse(contents: &str) -> Result<Self::Input, ParseError> {
+        Ok(Games {
+            games: parse_games(contents)?,
+            bag: Bag::standard(),
+        })
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1::legit_game_sum(&input.games, &input.bag)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2::power_sum(&input.games)
+    }
+
+    fn report(input: &Self::Input) -> Option<String> {
+        Some(
+            input
+                .games
+                .iter()
+                .map(|game| game.report(&input.bag) + "\n")
+                .collect(),
+        )
+    }
+
+    // `--bag` hands over the bag to check the games against
+    fn configure(input: &mut Self::Input, option: &dyn Any) {
+        if let Some(bag) = option.downcast_ref::<Bag>() {
+            input.bag = bag.clone();
+        }
     }
 }
 
 pub mod part1 {
     use super::*;
 
-    pub fn run() {
-        let contents = fs::read_to_string("inputs/day2.txt").expect("File not found");
+    pub fn legit_game_sum(games: &[Game], bag: &Bag) -> i32 {
+        games
+            .iter()
+            .filter(|game| game.is_legit(bag))
+            .map(|game| game.id)
+            .sum()
+    }
 
-        let mut result: i32 = 0;
-        let re = Regex::new(r"^Game ([0-9]+): (.*)$").unwrap();
-        for line in contents.split('\n') {
-            let (_, [game_id, reveals_str]) = re
-                .captures(line)
-                .expect("Failed to parse game ID")
-                .extract();
+    #[cfg(test)]
+    mod tests {
+        use super::*;
 
-            let mut reveals = Vec::new();
+        #[test]
+        fn test_day2_part1() {
+            assert_eq!(legit_game_sum(&Day2::parse("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green\nGame 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue\nGame 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red\nGame 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red\nGame 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green").unwrap().games, &Bag::standard()), 8);
+        }
 
-            for reveal in reveals_str.split(';') {
-                reveals.push(create_reveal(reveal))
-            }
+        #[test]
+        fn test_day2_custom_bag() {
+            let games = Day2::parse(
+                "Game 1: 3 blue, 4 red; 1 red, 2 green\nGame 2: 5 yellow, 1 red\nGame 3: 9 red",
+            )
+            .unwrap()
+            .games;
+
+            assert_eq!(
+                legit_game_sum(&games, &Bag::parse("red=4, green=2, blue=3").unwrap()),
+                1
+            );
+            assert_eq!(
+                legit_game_sum(&games, &Bag::parse("red=9\nyellow=5").unwrap()),
+                5
+            );
+            assert_eq!(legit_game_sum(&games, &Bag::default()), 0);
+        }
 
-            let game = Game {
-                id: game_id.to_string().parse::<i32>().unwrap(),
-                reveals,
-            };
+        #[test]
+        fn test_day2_bag_parse() {
+            let bag = Bag::parse("# a small bag\nred = 1\ngreen = 2\n").unwrap();
 
-            if game.is_legit() {
-                result += game.id;
-            }
+            assert_eq!(bag.to_string(), "2 green, 1 red");
+            assert_eq!(Bag::parse("red=1,green=2").unwrap(), bag);
+
+            let error = Bag::parse("red=1\ngreen:2").unwrap_err();
+            assert_eq!((error.line, error.text.as_str()), (2, "green:2"));
+        }
+
+        #[test]
+        fn test_day2_bag_load() {
+            let bag = Bag::load("inputs/day2-bag-example.txt").unwrap();
+
+            assert_eq!(bag, Bag::load("red=1,green=2").unwrap());
+            assert_eq!(
+                Bag::load("red=1,green:2").unwrap_err().to_string(),
+                "line 1, column 7: expected `=` in `green:2`"
+            );
+            assert!(matches!(
+                Bag::load("inputs/missing-bag.txt"),
+                Err(LoadError::Read { path, .. }) if path == "inputs/missing-bag.txt"
+            ));
+        }
+
+        #[test]
+        fn test_day2_report() {
+            let games = Day2::parse("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green\nGame 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green").unwrap().games;
+            let bag = Bag::standard();
+
+            assert_eq!(
+                games[0].report(&bag),
+                "Game 1: legit; minimal bag 6 blue, 2 green, 4 red; power 48"
+            );
+            assert_eq!(
+                games[1].report(&bag),
+                "Game 3: illegal, reveal 1 (6 blue, 8 green, 20 red) shows 20 red > 12; minimal bag 6 blue, 13 green, 20 red; power 1560"
+            );
+        }
+
+        #[test]
+        fn test_day2_parse_error() {
+            let error = Day2::parse("Game 1: 3 blue\nGame two: 1 red")
+                .err()
+                .unwrap();
+
+            assert_eq!(error.text, "Game two: 1 red");
+
+            let error = Day2::parse("Game 1: -3 blue").err().unwrap();
+            assert_eq!(error.text, "-3");
+            assert!(Day2::parse("Game 1: 4294967295 red, 1 red").is_err());
+            assert!(Bag::parse("red=-1").is_err());
         }
 
-        println!("Day 2 Part 1: {}", result)
+        #[test]
+        fn test_day2_power_past_i32() {
+            let games = Day2::parse("Game 1: 2000 red, 2000 green, 2000 blue").unwrap();
+
+            assert_eq!(Day2::part2(&games), 8_000_000_000);
+        }
     }
 }
 
 pub mod part2 {
     use super::*;
 
-    pub fn run() {
-        let contents = fs::read_to_string("inputs/day2.txt").expect("File not found");
-
-        let mut result: i32 = 0;
-        let re = Regex::new(r"^Game ([0-9]+): (.*)$").unwrap();
-        for line in contents.split('\n') {
-            let (_, [game_id, reveals_str]) = re
-                .captures(line)
-                .expect("Failed to parse game ID")
-                .extract();
-
-            let mut reveals = Vec::new();
-
-            for reveal in reveals_str.split(';') {
-                reveals.push(create_reveal(reveal))
-            }
+    pub fn power_sum(games: &[Game]) -> u64 {
+        games.iter().map(|game| game.power()).sum()
+    }
 
-            let game = Game {
-                id: game_id.to_string().parse::<i32>().unwrap(),
-                reveals,
-            };
+    #[cfg(test)]
+    mod tests {
+        use super::*;
 
-            result += game.power();
+        #[test]
+        fn test_day2_part2() {
+            assert_eq!(power_sum(&Day2::parse("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green\nGame 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue\nGame 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red\nGame 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red\nGame 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green").unwrap().games), 2286);
         }
-
-        println!("Day 2 Part 2: {}", result)
     }
 }
@@ -0,0 +1,302 @@
+use crate::{
+    error::{parse_lines, split_once, ParseError},
+    solution::{Optional, Solution},
+};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum Pulse {
+    Low,
+    High,
+}
+
+#[derive(Debug, Clone)]
+pub enum ModuleKind {
+    Broadcaster,
+    FlipFlop(bool),
+    Conjunction(HashMap<String, Pulse>),
+}
+
+#[derive(Debug, Clone)]
+pub struct Module {
+    pub name: String,
+    pub kind: ModuleKind,
+    pub destinations: Vec<String>,
+}
+
+impl Module {
+    pub fn parse(line: &str) -> Result<Self, ParseError> {
+        let (name, destinations) = split_once(line, " -> ")?;
+        let destinations = destinations.split(", ").map(|d| d.to_string()).collect();
+
+        let (name, kind) = if let Some(name) = name.strip_prefix('%') {
+            (name, ModuleKind::FlipFlop(false))
+        } else if let Some(name) = name.strip_prefix('&') {
+            (name, ModuleKind::Conjunction(HashMap::new()))
+        } else if name == "broadcaster" {
+            (name, ModuleKind::Broadcaster)
+        } else {
+            return Err(ParseError::new(
+                name,
+                "expected `broadcaster`, `%<name>` or `&<name>`, found",
+            ));
+        };
+
+        Ok(Self {
+            name: name.to_string(),
+            kind,
+            destinations,
+        })
+    }
+
+    pub fn receive(&mut self, from: &str, pulse: Pulse) -> Option<Pulse> {
+        match &mut self.kind {
+            ModuleKind::Broadcaster => Some(pulse),
+            ModuleKind::FlipFlop(on) => {
+                if pulse == Pulse::High {
+                    return None;
+                }
+
+                *on = !*on;
+                Some(if *on { Pulse::High } else { Pulse::Low })
+            }
+            ModuleKind::Conjunction(memory) => {
+                memory.insert(from.to_string(), pulse);
+
+                if memory.values().all(|&p| p == Pulse::High) {
+                    Some(Pulse::Low)
+                } else {
+                    Some(Pulse::High)
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Network {
+    pub modules: HashMap<String, Module>,
+}
+
+impl Network {
+    pub fn parse(contents: &str) -> Result<Self, ParseError> {
+        let mut modules: HashMap<String, Module> = parse_lines(contents, |line| {
+            Module::parse(line).map(|module| (module.name.clone(), module))
+        })?
+        .into_iter()
+        .collect();
+
+        // Conjunctions start out remembering a low pulse from every input
+        let edges: Vec<(String, String)> = modules
+            .values()
+            .flat_map(|module| {
+                module
+                    .destinations
+                    .iter()
+                    .map(|destination| (module.name.clone(), destination.clone()))
+            })
+            .collect();
+
+        for (from, to) in edges {
+            if let Some(Module {
+                kind: ModuleKind::Conjunction(memory),
+                ..
+            }) = modules.get_mut(&to)
+            {
+                memory.insert(from, Pulse::Low);
+            }
+        }
+
+        Ok(Self { modules })
+    }
+
+    pub fn inputs(&self, name: &str) -> Vec<String> {
+        self.modules
+            .values()
+            .filter(|module| module.destinations.iter().any(|d| d == name))
+            .map(|module| module.name.clone())
+            .collect()
+    }
+
+    // Every flip-flop's state and every conjunction's memory, in a fixed order, so two networks
+    // with the same state compare equal
+    pub fn state(&self) -> Vec<bool> {
+        let mut names: Vec<&String> = self.modules.keys().collect();
+        names.sort();
+
+        let mut state = Vec::new();
+        for name in names {
+            match &self.modules[name].kind {
+                ModuleKind::Broadcaster => {}
+                ModuleKind::FlipFlop(on) => state.push(*on),
+                ModuleKind::Conjunction(memory) => {
+                    let mut inputs: Vec<(&String, &Pulse)> = memory.iter().collect();
+                    inputs.sort_by_key(|(input, _)| *input);
+                    state.extend(inputs.iter().map(|(_, &pulse)| pulse == Pulse::High));
+                }
+            }
+        }
+
+        state
+    }
+
+    // Presses the button once and calls `observe` with every (from, to, pulse) sent
+    pub fn press_button<F>(&mut self, mut observe: F)
+    where
+        F: FnMut(&str, &str, Pulse),
+    {
+        let mut queue: VecDeque<(String, String, Pulse)> = VecDeque::new();
+        queue.push_back(("button".to_string(), "broadcaster".to_string(), Pulse::Low));
+
+        while let Some((from, to, pulse)) = queue.pop_front() {
+            observe(&from, &to, pulse);
+
+            if let Some(module) = self.modules.get_mut(&to) {
+                if let Some(next_pulse) = module.receive(&from, pulse) {
+                    for destination in &module.destinations {
+                        queue.push_back((to.clone(), destination.clone(), next_pulse));
+                    }
+                }
+            }
+        }
+    }
+}
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    type Input = Network;
+    type Answer1 = u64;
+    type Answer2 = Optional<u64>;
+
+    fn parse(contents: &str) -> Result<Self::Input, ParseError> {
+        Network::parse(contents)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1::pulse_product(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        Optional(part2::presses_until_low(input, "rx"))
+    }
+}
+
+pub mod part1 {
+    use super::*;
+
+    pub fn pulse_product(network: &Network) -> u64 {
+        let mut network = network.clone();
+        let mut low: u64 = 0;
+        let mut high: u64 = 0;
+
+        for _ in 0..1000 {
+            network.press_button(|_, _, pulse| match pulse {
+                Pulse::Low => low += 1,
+                Pulse::High => high += 1,
+            });
+        }
+
+        low * high
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_day20_part1a() {
+            assert_eq!(
+                pulse_product(
+                    &Day20::parse("broadcaster -> a, b, c\n%a -> b\n%b -> c\n%c -> inv\n&inv -> a")
+                        .unwrap()
+                ),
+                32000000
+            );
+        }
+
+        #[test]
+        fn test_day20_part1b() {
+            assert_eq!(
+                pulse_product(
+                    &Day20::parse(
+                        "broadcaster -> a\n%a -> inv, con\n&inv -> b\n%b -> con\n&con -> output"
+                    )
+                    .unwrap()
+                ),
+                11687500
+            );
+        }
+    }
+}
+
+pub mod part2 {
+    use super::*;
+    use num::integer::lcm;
+
+    // `rx` is fed by a single conjunction, so it receives a low pulse once every input of that
+    // conjunction has sent it a high pulse in the same press. Each input cycles independently.
+    // None if the target is not wired that way, or if the whole network comes back to a state
+    // it was already in before some input has fired, as that input then never will.
+    pub fn presses_until_low(network: &Network, target: &str) -> Option<u64> {
+        let mut network = network.clone();
+
+        let [feeder] = &network.inputs(target)[..] else {
+            return None;
+        };
+        let feeder = feeder.clone();
+        if !matches!(network.modules[&feeder].kind, ModuleKind::Conjunction(_)) {
+            return None;
+        }
+
+        let inputs = network.inputs(&feeder);
+        let mut cycles: HashMap<String, u64> = HashMap::new();
+        let mut seen: HashSet<Vec<bool>> = HashSet::new();
+
+        let mut presses: u64 = 0;
+        while cycles.len() < inputs.len() {
+            if !seen.insert(network.state()) {
+                return None;
+            }
+            presses += 1;
+
+            network.press_button(|from, to, pulse| {
+                if to == feeder && pulse == Pulse::High {
+                    cycles.entry(from.to_string()).or_insert(presses);
+                }
+            });
+        }
+
+        Some(cycles.values().fold(1, |acc, &cycle| lcm(acc, cycle)))
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_day20_part2() {
+            assert_eq!(
+                presses_until_low(&Day20::parse("broadcaster -> a\n%a -> b, ia\n%b -> ib\n&ia -> hub\n&ib -> hub\n&hub -> rx").unwrap(),
+                    "rx"
+                ),
+                Some(4)
+            );
+        }
+
+        #[test]
+        fn test_day20_part2_without_answer() {
+            let examples =
+                Day20::parse("broadcaster -> a, b, c\n%a -> b\n%b -> c\n%c -> inv\n&inv -> a")
+                    .unwrap();
+            // `ib` only ever hears from a flip-flop that stays off, so it never sends `hub` a high
+            let silent = Day20::parse(
+                "broadcaster -> a\n%a -> ia\n&ia -> hub\n&ib -> hub\n&hub -> rx\n%b -> ib",
+            )
+            .unwrap();
+
+            assert_eq!(Day20::part2(&examples).to_string(), "none");
+            assert_eq!(presses_until_low(&silent, "rx"), None);
+        }
+    }
+}
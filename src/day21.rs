@@ -0,0 +1,191 @@
+use crate::{error::ParseError, grid::Grid, solution::Solution};
+use std::collections::{HashSet, VecDeque};
+
+#[derive(Debug)]
+pub struct Garden {
+    pub rocks: Vec<Vec<bool>>,
+    pub start: (i64, i64),
+    pub height: i64,
+    pub width: i64,
+}
+
+impl Garden {
+    pub fn parse(contents: &str) -> Result<Self, ParseError> {
+        let tiles = Grid::parse(contents, |c| matches!(c, '.' | '#' | 'S').then_some(c))?;
+        let (x, y) = tiles
+            .iter()
+            .find(|(_, &value)| value == 'S')
+            .map(|(position, _)| position)
+            .ok_or_else(|| ParseError::new(contents, "missing start `S` in"))?;
+
+        let rocks: Vec<Vec<bool>> = tiles
+            .rows()
+            .map(|row| row.iter().map(|&value| value == '#').collect())
+            .collect();
+
+        Ok(Self {
+            rocks,
+            start: (x as i64, y as i64),
+            height: tiles.height() as i64,
+            width: tiles.width() as i64,
+        })
+    }
+
+    // The garden repeats infinitely in every direction
+    pub fn is_rock(&self, x: i64, y: i64) -> bool {
+        self.rocks[x.rem_euclid(self.height) as usize][y.rem_euclid(self.width) as usize]
+    }
+
+    pub fn count_reachable(&self, steps: u64, infinite: bool) -> u64 {
+        let mut visited: HashSet<(i64, i64)> = HashSet::new();
+        let mut queue: VecDeque<(i64, i64, u64)> = VecDeque::new();
+        let mut count = 0;
+
+        visited.insert(self.start);
+        queue.push_back((self.start.0, self.start.1, 0));
+
+        while let Some((x, y, distance)) = queue.pop_front() {
+            // Any tile reached in time can be revisited by stepping back and forth
+            if distance % 2 == steps % 2 {
+                count += 1;
+            }
+
+            if distance == steps {
+                continue;
+            }
+
+            for (dx, dy) in [(-1, 0), (1, 0), (0, -1), (0, 1)] {
+                let nx = x + dx;
+                let ny = y + dy;
+
+                if !infinite && (nx < 0 || ny < 0 || nx >= self.height || ny >= self.width) {
+                    continue;
+                }
+
+                if self.is_rock(nx, ny) || !visited.insert((nx, ny)) {
+                    continue;
+                }
+
+                queue.push_back((nx, ny, distance + 1));
+            }
+        }
+
+        count
+    }
+
+    // The reachable count grows quadratically once the step count is sampled at whole garden
+    // widths, so three samples are enough to fit it. This needs the start row and column to be
+    // free of rocks, which holds for the real input but not for the example.
+    pub fn extrapolate_reachable(&self, steps: u64) -> u64 {
+        let width = self.width as u64;
+        let offset = steps % width;
+
+        if steps < offset + 2 * width {
+            return self.count_reachable(steps, true);
+        }
+
+        let y0 = self.count_reachable(offset, true) as i64;
+        let y1 = self.count_reachable(offset + width, true) as i64;
+        let y2 = self.count_reachable(offset + 2 * width, true) as i64;
+
+        let n = ((steps - offset) / width) as i64;
+        let first = y1 - y0;
+        let second = y2 - 2 * y1 + y0;
+
+        (y0 + first * n + second * n * (n - 1) / 2) as u64
+    }
+}
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    type Input = Garden;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(contents: &str) -> Result<Self::Input, ParseError> {
+        Garden::parse(contents)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1::reachable_plots(input, 64)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2::reachable_plots(input, 26501365)
+    }
+}
+
+pub mod part1 {
+    use super::*;
+
+    pub fn reachable_plots(garden: &Garden, steps: u64) -> u64 {
+        garden.count_reachable(steps, false)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_day21_part1() {
+            assert_eq!(reachable_plots(&Day21::parse("...........\n.....###.#.\n.###.##..#.\n..#.#...#..\n....#.#....\n.##..S####.\n.##..#...#.\n.......##..\n.##.#.####.\n.##..##.##.\n...........").unwrap(), 6), 16);
+        }
+    }
+}
+
+pub mod part2 {
+    use super::*;
+
+    pub fn reachable_plots(garden: &Garden, steps: u64) -> u64 {
+        garden.extrapolate_reachable(steps)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        const EXAMPLE: &str = "...........\n.....###.#.\n.###.##..#.\n..#.#...#..\n....#.#....\n.##..S####.\n.##..#...#.\n.......##..\n.##.#.####.\n.##..##.##.\n...........";
+
+        #[test]
+        fn test_day21_part2a() {
+            assert_eq!(Garden::parse(EXAMPLE).unwrap().count_reachable(6, true), 16);
+        }
+
+        #[test]
+        fn test_day21_part2b() {
+            assert_eq!(
+                Garden::parse(EXAMPLE).unwrap().count_reachable(10, true),
+                50
+            );
+        }
+
+        #[test]
+        fn test_day21_part2c() {
+            assert_eq!(
+                Garden::parse(EXAMPLE).unwrap().count_reachable(50, true),
+                1594
+            );
+        }
+
+        #[test]
+        fn test_day21_part2d() {
+            assert_eq!(
+                Garden::parse(EXAMPLE).unwrap().count_reachable(100, true),
+                6536
+            );
+        }
+
+        #[test]
+        fn test_day21_part2e() {
+            let garden =
+                Day21::parse(".......\n.#...#.\n..#....\n...S...\n....#..\n.#...#.\n.......")
+                    .unwrap();
+
+            assert_eq!(
+                reachable_plots(&garden, 3 + 7 * 6),
+                garden.count_reachable(3 + 7 * 6, true)
+            );
+        }
+    }
+}
@@ -0,0 +1,202 @@
+use crate::{
+    error::{number, parse_lines, split_once, ParseError},
+    solution::Solution,
+};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+#[derive(Debug, Clone)]
+pub struct Brick {
+    pub start: (usize, usize, usize),
+    pub end: (usize, usize, usize),
+}
+
+impl Brick {
+    pub fn parse(line: &str) -> Result<Self, ParseError> {
+        let (start, end) = split_once(line, "~")?;
+
+        Ok(Self {
+            start: Brick::parse_point(start)?,
+            end: Brick::parse_point(end)?,
+        })
+    }
+
+    pub fn parse_point(point: &str) -> Result<(usize, usize, usize), ParseError> {
+        let coordinates: Vec<usize> = point.split(',').map(number).collect::<Result<_, _>>()?;
+
+        match coordinates[..] {
+            [x, y, z] => Ok((x, y, z)),
+            _ => Err(ParseError::new(point, "expected `<x>,<y>,<z>`, found")),
+        }
+    }
+
+    pub fn bottom(&self) -> usize {
+        self.start.2.min(self.end.2)
+    }
+
+    pub fn height(&self) -> usize {
+        self.start.2.abs_diff(self.end.2)
+    }
+
+    pub fn footprint(&self) -> Vec<(usize, usize)> {
+        let mut cells = vec![];
+
+        for x in self.start.0.min(self.end.0)..=self.start.0.max(self.end.0) {
+            for y in self.start.1.min(self.end.1)..=self.start.1.max(self.end.1) {
+                cells.push((x, y));
+            }
+        }
+
+        cells
+    }
+}
+
+#[derive(Debug)]
+pub struct Stack {
+    // supports[i] are the bricks resting on brick i, supported_by[i] the bricks it rests on
+    pub supports: Vec<HashSet<usize>>,
+    pub supported_by: Vec<HashSet<usize>>,
+}
+
+impl Stack {
+    pub fn parse(contents: &str) -> Result<Self, ParseError> {
+        let mut bricks: Vec<Brick> = parse_lines(contents, Brick::parse)?;
+        bricks.sort_by_key(|brick| brick.bottom());
+
+        let mut supports: Vec<HashSet<usize>> = vec![HashSet::new(); bricks.len()];
+        let mut supported_by: Vec<HashSet<usize>> = vec![HashSet::new(); bricks.len()];
+
+        // Highest settled (z, brick) for each (x, y) column
+        let mut heights: HashMap<(usize, usize), (usize, usize)> = HashMap::new();
+
+        for (i, brick) in bricks.iter().enumerate() {
+            let footprint = brick.footprint();
+
+            let floor = footprint
+                .iter()
+                .filter_map(|cell| heights.get(cell))
+                .map(|(z, _)| *z)
+                .max()
+                .unwrap_or(0);
+
+            for cell in &footprint {
+                if let Some(&(z, below)) = heights.get(cell) {
+                    if z == floor {
+                        supports[below].insert(i);
+                        supported_by[i].insert(below);
+                    }
+                }
+            }
+
+            let top = floor + 1 + brick.height();
+            for cell in footprint {
+                heights.insert(cell, (top, i));
+            }
+        }
+
+        Ok(Self {
+            supports,
+            supported_by,
+        })
+    }
+
+    pub fn is_safe_to_disintegrate(&self, brick: usize) -> bool {
+        self.supports[brick]
+            .iter()
+            .all(|&above| self.supported_by[above].len() > 1)
+    }
+
+    pub fn count_falling(&self, brick: usize) -> usize {
+        let mut fallen: HashSet<usize> = HashSet::from([brick]);
+        let mut queue: VecDeque<usize> = VecDeque::from([brick]);
+
+        while let Some(current) = queue.pop_front() {
+            for &above in &self.supports[current] {
+                if fallen.contains(&above) {
+                    continue;
+                }
+
+                if self.supported_by[above].is_subset(&fallen) {
+                    fallen.insert(above);
+                    queue.push_back(above);
+                }
+            }
+        }
+
+        // The disintegrated brick itself does not count
+        fallen.len() - 1
+    }
+}
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    type Input = Stack;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(contents: &str) -> Result<Self::Input, ParseError> {
+        Stack::parse(contents)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1::count_safe_bricks(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2::sum_falling_bricks(input)
+    }
+}
+
+pub mod part1 {
+    use super::*;
+
+    pub fn count_safe_bricks(stack: &Stack) -> usize {
+        (0..stack.supports.len())
+            .filter(|&brick| stack.is_safe_to_disintegrate(brick))
+            .count()
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_day22_part1() {
+            assert_eq!(
+                count_safe_bricks(&Day22::parse("1,0,1~1,2,1\n0,0,2~2,0,2\n0,2,3~2,2,3\n0,0,4~0,2,4\n2,0,5~2,2,5\n0,1,6~2,1,6\n1,1,8~1,1,9").unwrap()),
+                5
+            );
+        }
+
+        #[test]
+        fn test_day22_part1_tower() {
+            assert_eq!(
+                count_safe_bricks(&Day22::parse("0,0,1~0,0,1\n0,0,5~0,0,6\n0,0,3~0,0,3").unwrap()),
+                1
+            );
+        }
+    }
+}
+
+pub mod part2 {
+    use super::*;
+
+    pub fn sum_falling_bricks(stack: &Stack) -> usize {
+        (0..stack.supports.len())
+            .map(|brick| stack.count_falling(brick))
+            .sum()
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_day22_part2() {
+            assert_eq!(
+                sum_falling_bricks(&Day22::parse("1,0,1~1,2,1\n0,0,2~2,0,2\n0,2,3~2,2,3\n0,0,4~0,2,4\n2,0,5~2,2,5\n0,1,6~2,1,6\n1,1,8~1,1,9").unwrap()),
+                7
+            );
+        }
+    }
+}
@@ -0,0 +1,201 @@
+use crate::{error::ParseError, grid::Grid, solution::Solution};
+use std::collections::HashMap;
+
+#[derive(Debug)]
+pub struct Trails {
+    pub tiles: Vec<Vec<char>>,
+    pub start: (usize, usize),
+    pub end: (usize, usize),
+}
+
+impl Trails {
+    pub fn parse(contents: &str) -> Result<Self, ParseError> {
+        let grid = Grid::parse(contents, |c| {
+            matches!(c, '.' | '#' | '^' | 'v' | '<' | '>').then_some(c)
+        })?;
+        let tiles: Vec<Vec<char>> = grid.rows().map(|row| row.to_vec()).collect();
+
+        // The path enters through the gap in the top row and leaves through the one in the bottom.
+        // Every accepted tile is ASCII, so byte offsets are columns.
+        let gap = |line: &str| {
+            line.find('.')
+                .ok_or_else(|| ParseError::new(line, "missing a `.` gap in"))
+        };
+
+        let last = tiles.len().saturating_sub(1);
+        let line = |index| contents.lines().nth(index).unwrap_or(contents);
+        let start = (0, gap(line(0)).map_err(|error| error.on_line(0, line(0)))?);
+        let end = (
+            last,
+            gap(line(last)).map_err(|error| error.on_line(last, line(last)))?,
+        );
+
+        Ok(Self { tiles, start, end })
+    }
+
+    fn neighbours(&self, x: usize, y: usize, slippery: bool) -> Vec<(usize, usize)> {
+        let allowed: &[(isize, isize)] = match (slippery, self.tiles[x][y]) {
+            (true, '^') => &[(-1, 0)],
+            (true, 'v') => &[(1, 0)],
+            (true, '<') => &[(0, -1)],
+            (true, '>') => &[(0, 1)],
+            _ => &[(-1, 0), (1, 0), (0, -1), (0, 1)],
+        };
+
+        allowed
+            .iter()
+            .filter_map(|(dx, dy)| {
+                let nx = x.checked_add_signed(*dx)?;
+                let ny = y.checked_add_signed(*dy)?;
+
+                match self.tiles.get(nx)?.get(ny)? {
+                    '#' => None,
+                    _ => Some((nx, ny)),
+                }
+            })
+            .collect()
+    }
+
+    // Collapses the corridors between junctions into weighted edges
+    fn junction_graph(&self, slippery: bool) -> Vec<Vec<(usize, usize)>> {
+        let mut junctions: Vec<(usize, usize)> = vec![self.start, self.end];
+        for (x, row) in self.tiles.iter().enumerate() {
+            for (y, tile) in row.iter().enumerate() {
+                if *tile != '#' && self.neighbours(x, y, false).len() > 2 {
+                    junctions.push((x, y));
+                }
+            }
+        }
+
+        let index: HashMap<(usize, usize), usize> = junctions
+            .iter()
+            .enumerate()
+            .map(|(i, &junction)| (junction, i))
+            .collect();
+
+        let mut graph: Vec<Vec<(usize, usize)>> = vec![vec![]; junctions.len()];
+
+        for (i, &junction) in junctions.iter().enumerate() {
+            for first in self.neighbours(junction.0, junction.1, slippery) {
+                let mut previous = junction;
+                let mut current = first;
+                let mut length = 1;
+
+                loop {
+                    if let Some(&j) = index.get(&current) {
+                        graph[i].push((j, length));
+                        break;
+                    }
+
+                    let next: Vec<(usize, usize)> = self
+                        .neighbours(current.0, current.1, slippery)
+                        .into_iter()
+                        .filter(|&n| n != previous)
+                        .collect();
+
+                    // Dead end, or a slope pointing back the way we came
+                    if next.is_empty() {
+                        break;
+                    }
+
+                    previous = current;
+                    current = next[0];
+                    length += 1;
+                }
+            }
+        }
+
+        graph
+    }
+
+    pub fn longest_hike(&self, slippery: bool) -> usize {
+        let graph = self.junction_graph(slippery);
+        let mut visited = vec![false; graph.len()];
+
+        longest_path(&graph, 0, 1, &mut visited).unwrap_or(0)
+    }
+}
+
+fn longest_path(
+    graph: &[Vec<(usize, usize)>],
+    node: usize,
+    target: usize,
+    visited: &mut [bool],
+) -> Option<usize> {
+    if node == target {
+        return Some(0);
+    }
+
+    visited[node] = true;
+
+    let mut longest: Option<usize> = None;
+    for &(next, length) in &graph[node] {
+        if visited[next] {
+            continue;
+        }
+
+        if let Some(rest) = longest_path(graph, next, target, visited) {
+            longest = Some(longest.unwrap_or(0).max(rest + length));
+        }
+    }
+
+    visited[node] = false;
+
+    longest
+}
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    type Input = Trails;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(contents: &str) -> Result<Self::Input, ParseError> {
+        Trails::parse(contents)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1::longest_hike(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2::longest_hike(input)
+    }
+}
+
+pub mod part1 {
+    use super::*;
+
+    pub fn longest_hike(trails: &Trails) -> usize {
+        trails.longest_hike(true)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_day23_part1() {
+            assert_eq!(longest_hike(&Day23::parse("#.#####################\n#.......#########...###\n#######.#########.#.###\n###.....#.>.>.###.#.###\n###v#####.#v#.###.#.###\n###.>...#.#.#.....#...#\n###v###.#.#.#########.#\n###...#.#.#.......#...#\n#####.#.#.#######.#.###\n#.....#.#.#.......#...#\n#.#####.#.#.#########v#\n#.#...#...#...###...>.#\n#.#.#v#######v###.###v#\n#...#.>.#...>.>.#.###.#\n#####v#.#.###v#.#.###.#\n#.....#...#...#.#.#...#\n#.#########.###.#.#.###\n#...###...#...#...#.###\n###.###.#.###v#####v###\n#...#...#.#.>.>.#.>.###\n#.###.###.#.###.#.#v###\n#.....###...###...#...#\n#####################.#").unwrap()), 94);
+        }
+    }
+}
+
+pub mod part2 {
+    use super::*;
+
+    pub fn longest_hike(trails: &Trails) -> usize {
+        trails.longest_hike(false)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_day23_part2() {
+            assert_eq!(longest_hike(&Day23::parse("#.#####################\n#.......#########...###\n#######.#########.#.###\n###.....#.>.>.###.#.###\n###v#####.#v#.###.#.###\n###.>...#.#.#.....#...#\n###v###.#.#.#########.#\n###...#.#.#.......#...#\n#####.#.#.#######.#.###\n#.....#.#.#.......#...#\n#.#####.#.#.#########v#\n#.#...#...#...###...>.#\n#.#.#v#######v###.###v#\n#...#.>.#...>.>.#.###.#\n#####v#.#.###v#.#.###.#\n#.....#...#...#.#.#...#\n#.#########.###.#.#.###\n#...###...#...#...#.###\n###.###.#.###v#####v###\n#...#...#.#.>.>.#.>.###\n#.###.###.#.###.#.#v###\n#.....###...###...#...#\n#####################.#").unwrap()), 154);
+        }
+    }
+}
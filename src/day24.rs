@@ -0,0 +1,236 @@
+use crate::{
+    error::{number, parse_lines, split_once, ParseError},
+    solution::{Optional, Solution},
+};
+use num::{BigInt, BigRational, One, Zero};
+
+#[derive(Debug, Clone, Copy)]
+pub struct Hailstone {
+    pub position: [i128; 3],
+    pub velocity: [i128; 3],
+}
+
+impl Hailstone {
+    pub fn parse(line: &str) -> Result<Self, ParseError> {
+        let (position, velocity) = split_once(line, "@")?;
+
+        Ok(Self {
+            position: Hailstone::parse_vector(position)?,
+            velocity: Hailstone::parse_vector(velocity)?,
+        })
+    }
+
+    pub fn parse_vector(vector: &str) -> Result<[i128; 3], ParseError> {
+        let values: Vec<i128> = vector.split(',').map(number).collect::<Result<_, _>>()?;
+
+        values
+            .try_into()
+            .map_err(|_| ParseError::new(vector.trim(), "expected `<x>, <y>, <z>`, found"))
+    }
+
+    // Checks whether the future xy paths of two hailstones cross within min..=max, using
+    // exact integer arithmetic scaled by the determinant instead of floating point
+    pub fn crosses_within(&self, other: &Hailstone, min: i128, max: i128) -> bool {
+        let [px, py, _] = self.position;
+        let [vx, vy, _] = self.velocity;
+        let [qx, qy, _] = other.position;
+        let [wx, wy, _] = other.velocity;
+
+        let mut det = vx * wy - vy * wx;
+        if det == 0 {
+            return false;
+        }
+
+        let (dx, dy) = (qx - px, qy - py);
+        let mut t = dx * wy - dy * wx;
+        let mut s = dx * vy - dy * vx;
+
+        if det < 0 {
+            det = -det;
+            t = -t;
+            s = -s;
+        }
+
+        if t < 0 || s < 0 {
+            return false;
+        }
+
+        let x = px * det + vx * t;
+        let y = py * det + vy * t;
+
+        x >= min * det && x <= max * det && y >= min * det && y <= max * det
+    }
+}
+
+fn cross(a: [i128; 3], b: [i128; 3]) -> [i128; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+// Every hailstone satisfies (P - p) x (V - v) = 0 for the rock position P and velocity V.
+// Subtracting that equation for two hailstones cancels the non-linear P x V term, leaving
+// three linear equations per pair.
+fn rock_equations(a: &Hailstone, b: &Hailstone) -> Vec<(Vec<i128>, i128)> {
+    let dp: Vec<i128> = (0..3).map(|i| b.position[i] - a.position[i]).collect();
+    let dv: Vec<i128> = (0..3).map(|i| b.velocity[i] - a.velocity[i]).collect();
+
+    let rhs_a = cross(a.position, a.velocity);
+    let rhs_b = cross(b.position, b.velocity);
+
+    vec![
+        (
+            vec![0, dv[2], -dv[1], 0, -dp[2], dp[1]],
+            rhs_b[0] - rhs_a[0],
+        ),
+        (
+            vec![-dv[2], 0, dv[0], dp[2], 0, -dp[0]],
+            rhs_b[1] - rhs_a[1],
+        ),
+        (
+            vec![dv[1], -dv[0], 0, -dp[1], dp[0], 0],
+            rhs_b[2] - rhs_a[2],
+        ),
+    ]
+}
+
+fn solve(equations: Vec<(Vec<i128>, i128)>) -> Option<Vec<BigRational>> {
+    let size = equations.len();
+    let mut matrix: Vec<Vec<BigRational>> = equations
+        .into_iter()
+        .map(|(coefficients, rhs)| {
+            coefficients
+                .into_iter()
+                .chain(std::iter::once(rhs))
+                .map(|v| BigRational::from_integer(BigInt::from(v)))
+                .collect()
+        })
+        .collect();
+
+    for column in 0..size {
+        let pivot = (column..size).find(|&row| !matrix[row][column].is_zero())?;
+        matrix.swap(column, pivot);
+
+        let divisor = matrix[column][column].clone();
+        for value in matrix[column].iter_mut() {
+            *value = &*value / &divisor;
+        }
+
+        for row in 0..size {
+            if row == column || matrix[row][column].is_zero() {
+                continue;
+            }
+
+            let factor = matrix[row][column].clone();
+            let pivot_row = matrix[column].clone();
+            for (value, pivot_value) in matrix[row].iter_mut().zip(pivot_row).skip(column) {
+                *value = &*value - &factor * pivot_value;
+            }
+        }
+    }
+
+    debug_assert!((0..size).all(|i| matrix[i][i] == BigRational::one()));
+
+    Some(matrix.into_iter().map(|row| row[size].clone()).collect())
+}
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    type Input = Vec<Hailstone>;
+    type Answer1 = usize;
+    type Answer2 = Optional<i128>;
+
+    fn parse(contents: &str) -> Result<Self::Input, ParseError> {
+        parse_lines(contents, Hailstone::parse)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1::count_crossings(input, 200000000000000, 400000000000000)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        Optional(part2::rock_position_sum(input))
+    }
+}
+
+pub mod part1 {
+    use super::*;
+
+    pub fn count_crossings(hailstones: &[Hailstone], min: i128, max: i128) -> usize {
+        let mut count = 0;
+        for i in 0..hailstones.len() {
+            for j in i + 1..hailstones.len() {
+                if hailstones[i].crosses_within(&hailstones[j], min, max) {
+                    count += 1;
+                }
+            }
+        }
+
+        count
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_day24_part1() {
+            assert_eq!(
+                count_crossings(&Day24::parse("19, 13, 30 @ -2,  1, -2\n18, 19, 22 @ -1, -1, -2\n20, 25, 34 @ -2, -2, -4\n12, 31, 28 @ -1, -2, -1\n20, 19, 15 @  1, -5, -3").unwrap(),
+                    7,
+                    27
+                ),
+                2
+            );
+        }
+    }
+}
+
+pub mod part2 {
+    use super::*;
+
+    // None when no triple of hailstones pins down a single rock trajectory
+    pub fn rock_position_sum(hailstones: &[Hailstone]) -> Option<i128> {
+        // Some triples of hailstones are degenerate, so keep trying until one pins down the rock
+        for i in 1..hailstones.len() {
+            for j in i + 1..hailstones.len() {
+                let mut equations = rock_equations(&hailstones[0], &hailstones[i]);
+                equations.extend(rock_equations(&hailstones[0], &hailstones[j]));
+
+                if let Some(solution) = solve(equations) {
+                    let sum: BigRational = solution.iter().take(3).sum();
+
+                    return sum.to_integer().try_into().ok();
+                }
+            }
+        }
+
+        None
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_day24_part2() {
+            assert_eq!(
+                rock_position_sum(&Day24::parse("19, 13, 30 @ -2,  1, -2\n18, 19, 22 @ -1, -1, -2\n20, 25, 34 @ -2, -2, -4\n12, 31, 28 @ -1, -2, -1\n20, 19, 15 @  1, -5, -3").unwrap()
+                ),
+                Some(47)
+            );
+        }
+
+        #[test]
+        fn test_day24_part2_underdetermined() {
+            let hailstones =
+                Day24::parse("19, 13, 30 @ -2,  1, -2\n18, 19, 22 @ -1, -1, -2").unwrap();
+
+            assert_eq!(rock_position_sum(&hailstones), None);
+            assert_eq!(Day24::part2(&hailstones).to_string(), "none");
+        }
+    }
+}
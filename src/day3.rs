@@ -1,106 +1,210 @@
-pub mod part1 {
-    use std::fs;
-    use substring::Substring;
-
-    fn is_symbol(letter: char) -> bool {
-        !matches!(
-            letter,
-            '0' | '1' | '2' | '3' | '4' | '5' | '6' | '7' | '8' | '9' | '.'
-        )
+use crate::{error::ParseError, grid::Grid, solution::Solution};
+use std::ops::Range;
+
+// Rows may be ragged, so shorter rows are padded with empty space
+pub fn parse_schematic(contents: &str) -> Result<Grid<char>, ParseError> {
+    for (row, line) in contents.lines().enumerate() {
+        if let Some((column, c)) = line.chars().enumerate().find(|(_, c)| !c.is_ascii()) {
+            return Err(
+                ParseError::new(&c.to_string(), "unexpected character").at(row + 1, column + 1)
+            );
+        }
     }
 
-    fn is_symbol_in_range(line: &str, start: i32, end: i32) -> bool {
-        let chars: Vec<char> = line.chars().collect();
-        let max = (chars.len() as i32) - 1;
+    let width = contents.lines().map(|line| line.len()).max().unwrap_or(0);
+    let height = contents.lines().count();
 
-        for i in start..(end + 1) {
-            if i >= 0 && i <= max && is_symbol(chars[i as usize]) {
-                return true;
-            }
-        }
+    let cells = contents
+        .lines()
+        .flat_map(|line| format!("{:.<width$}", line, width = width).into_bytes())
+        .map(char::from)
+        .collect();
 
-        false
-    }
+    Ok(Grid::new(width, height, cells))
+}
 
-    fn engine_schematic_sum(schematic: &str) -> i32 {
-        let mut result: i32 = 0;
-        let lines: Vec<&str> = schematic.lines().collect();
-        let lines_max = (lines.len() - 1) as i32;
-
-        for (line_idx, line) in schematic.lines().enumerate() {
-            let chars_len = line.len();
-            let mut digit_start_idx: i32 = -1;
-            let mut digit_end_idx: i32 = -1;
-
-            for (letter_idx, letter) in line.chars().enumerate() {
-                match letter.to_string().parse::<i32>() {
-                    Ok(_digit) => {
-                        if digit_start_idx < 0 {
-                            digit_start_idx = letter_idx as i32;
-                        }
-                    }
-                    Err(_error) => {
-                        if digit_start_idx >= 0 {
-                            // The previous index was the last numeric letter
-                            digit_end_idx = (letter_idx as i32) - 1;
-                        }
-                    }
-                }
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartNumber {
+    pub value: i32,
+    pub row: usize,
+    pub columns: Range<usize>,
+}
 
-                // Handle end of line
-                if digit_start_idx >= 0 && digit_end_idx < 0 && letter_idx == chars_len - 1 {
-                    digit_end_idx = letter_idx as i32;
-                }
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Symbol {
+    pub glyph: char,
+    pub row: usize,
+    pub column: usize,
+}
 
-                if digit_start_idx >= 0 && digit_end_idx >= 0 && digit_end_idx >= digit_start_idx {
-                    let digit =
-                        line.substring(digit_start_idx as usize, (digit_end_idx as usize) + 1);
+// Every number and symbol of the schematic, with the numbers each symbol touches (including
+// diagonally) and the symbols each number touches. Both lists hold indices and are sorted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Schematic {
+    pub numbers: Vec<PartNumber>,
+    pub symbols: Vec<Symbol>,
+    symbol_numbers: Vec<Vec<usize>>,
+    number_symbols: Vec<Vec<usize>>,
+}
 
-                    let prev_line_idx = (line_idx as i32) - 1;
-                    let next_line_idx = (line_idx as i32) + 1;
+impl Schematic {
+    pub fn parse(contents: &str) -> Result<Self, ParseError> {
+        Self::from_grid(&parse_schematic(contents)?)
+    }
 
-                    let symbol_found_prev_line = prev_line_idx >= 0
-                        && is_symbol_in_range(
-                            lines[prev_line_idx as usize],
-                            digit_start_idx - 1,
-                            digit_end_idx + 1,
-                        );
+    // Fails on a number that does not fit in an `i32`
+    pub fn from_grid(grid: &Grid<char>) -> Result<Self, ParseError> {
+        let mut numbers = Vec::new();
+        let mut symbols = Vec::new();
+        let mut owners: Grid<Option<usize>> = Grid::filled(grid.width(), grid.height(), None);
 
-                    let symbol_found_curr_line =
-                        is_symbol_in_range(line, digit_start_idx - 1, digit_end_idx + 1);
+        for row in 0..grid.height() {
+            let mut column = 0;
 
-                    let symbol_found_next_line = next_line_idx <= lines_max
-                        && is_symbol_in_range(
-                            lines[next_line_idx as usize],
-                            digit_start_idx - 1,
-                            digit_end_idx + 1,
-                        );
+            while column < grid.width() {
+                let letter = grid[(row, column)];
 
-                    let symbol_found =
-                        symbol_found_prev_line || symbol_found_curr_line || symbol_found_next_line;
+                if letter.is_ascii_digit() {
+                    let start = column;
+                    let mut value = Some(0i32);
 
-                    if symbol_found {
-                        match digit.parse::<i32>() {
-                            Ok(digit) => result += digit,
-                            Err(_error) => {}
-                        }
+                    while let Some(digit) = grid.get(row, column).and_then(|c| c.to_digit(10)) {
+                        value = value
+                            .and_then(|value| value.checked_mul(10))
+                            .and_then(|value| value.checked_add(digit as i32));
+                        owners[(row, column)] = Some(numbers.len());
+                        column += 1;
                     }
 
-                    digit_start_idx = -1;
-                    digit_end_idx = -1;
+                    let Some(value) = value else {
+                        let digits: String = (start..column).map(|c| grid[(row, c)]).collect();
+                        return Err(
+                            ParseError::new(&digits, "number too large").at(row + 1, start + 1)
+                        );
+                    };
+
+                    numbers.push(PartNumber {
+                        value,
+                        row,
+                        columns: start..column,
+                    });
+                    continue;
+                }
+
+                if letter != '.' {
+                    symbols.push(Symbol {
+                        glyph: letter,
+                        row,
+                        column,
+                    });
                 }
+
+                column += 1;
             }
         }
 
-        result
+        let mut number_symbols = vec![Vec::new(); numbers.len()];
+        let symbol_numbers: Vec<Vec<usize>> = symbols
+            .iter()
+            .enumerate()
+            .map(|(index, symbol)| {
+                let mut touching: Vec<usize> = grid
+                    .neighbours8((symbol.row, symbol.column))
+                    .filter_map(|position| owners[position])
+                    .collect();
+                touching.sort_unstable();
+                touching.dedup();
+
+                for &number in &touching {
+                    number_symbols[number].push(index);
+                }
+
+                touching
+            })
+            .collect();
+
+        Ok(Self {
+            numbers,
+            symbols,
+            symbol_numbers,
+            number_symbols,
+        })
     }
 
-    pub fn run() {
-        let contents = fs::read_to_string("inputs/day3.txt").expect("File not found");
+    // Indices into `numbers` of the numbers touching the symbol at `symbol`
+    pub fn neighbours_of_symbol(&self, symbol: usize) -> &[usize] {
+        &self.symbol_numbers[symbol]
+    }
+
+    // Indices into `symbols` of the symbols touching the number at `number`
+    pub fn neighbours_of_number(&self, number: usize) -> &[usize] {
+        &self.number_symbols[number]
+    }
+
+    // Numbers touching at least one symbol
+    pub fn part_numbers(&self) -> impl Iterator<Item = &PartNumber> {
+        self.numbers
+            .iter()
+            .zip(&self.number_symbols)
+            .filter(|(_, symbols)| !symbols.is_empty())
+            .map(|(number, _)| number)
+    }
 
-        let result = engine_schematic_sum(&contents);
+    // Numbers touching at least one `glyph`, each listed once in reading order
+    pub fn numbers_touching(&self, glyph: char) -> Vec<&PartNumber> {
+        self.numbers
+            .iter()
+            .zip(&self.number_symbols)
+            .filter(|(_, symbols)| symbols.iter().any(|&s| self.symbols[s].glyph == glyph))
+            .map(|(number, _)| number)
+            .collect()
+    }
 
-        println!("Day 3 Part 1: {}", result);
+    // Symbols touching exactly `count` distinct numbers
+    pub fn symbols_with_neighbours(&self, count: usize) -> Vec<&Symbol> {
+        self.symbols
+            .iter()
+            .zip(&self.symbol_numbers)
+            .filter(|(_, numbers)| numbers.len() == count)
+            .map(|(symbol, _)| symbol)
+            .collect()
+    }
+
+    // Products of the two numbers touching each `*` that touches exactly two numbers
+    pub fn gear_ratios(&self) -> impl Iterator<Item = i32> + '_ {
+        self.symbols
+            .iter()
+            .zip(&self.symbol_numbers)
+            .filter(|(symbol, numbers)| symbol.glyph == '*' && numbers.len() == 2)
+            .map(|(_, numbers)| numbers.iter().map(|&n| self.numbers[n].value).product())
+    }
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = Schematic;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(contents: &str) -> Result<Self::Input, ParseError> {
+        Schematic::parse(contents)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1::engine_schematic_sum(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2::gear_ratios(input)
+    }
+}
+
+pub mod part1 {
+    use super::*;
+
+    pub fn engine_schematic_sum(schematic: &Schematic) -> i32 {
+        schematic.part_numbers().map(|number| number.value).sum()
     }
 
     #[cfg(test)]
@@ -110,7 +214,7 @@ pub mod part1 {
         #[test]
         fn test_engine_schematic_sum() {
             assert_eq!(
-                engine_schematic_sum("467..114..\n...*......\n..35..633.\n......#...\n617*......\n.....+.58.\n..592.....\n......755.\n...$.*....\n.664.598.."),
+                engine_schematic_sum(&Day3::parse("467..114..\n...*......\n..35..633.\n......#...\n617*......\n.....+.58.\n..592.....\n......755.\n...$.*....\n.664.598..").unwrap()),
                 4361
             );
         }
@@ -118,7 +222,7 @@ pub mod part1 {
         #[test]
         fn test_engine_schematic_sum2() {
             assert_eq!(
-              engine_schematic_sum("...*......\n467..114..\n..........\n...*......\n..35..633.\n......#...\n617*......\n.....+.58.\n..592.....\n......755.\n...$.*....\n.664.598.."),
+              engine_schematic_sum(&Day3::parse("...*......\n467..114..\n..........\n...*......\n..35..633.\n......#...\n617*......\n.....+.58.\n..592.....\n......755.\n...$.*....\n.664.598..").unwrap()),
               4361
           );
         }
@@ -126,83 +230,118 @@ pub mod part1 {
         #[test]
         fn test_engine_schematic_sum3() {
             assert_eq!(
-              engine_schematic_sum("...*......\n467..114..\n..........\n...*......\n..35..633.\n......#...\n617*......\n.....+.58.\n..592.....\n......755.\n...$.*....\n..........\n.664.598..\n...$.*...."),
+              engine_schematic_sum(&Day3::parse("...*......\n467..114..\n..........\n...*......\n..35..633.\n......#...\n617*......\n.....+.58.\n..592.....\n......755.\n...$.*....\n..........\n.664.598..\n...$.*....").unwrap()),
               4361
           );
         }
 
         #[test]
         fn test_engine_schematic_sum4a() {
-            assert_eq!(engine_schematic_sum("............*975"), 975);
+            assert_eq!(
+                engine_schematic_sum(&Day3::parse("............*975").unwrap()),
+                975
+            );
         }
 
         #[test]
         fn test_engine_schematic_sum4b() {
             assert_eq!(
-                engine_schematic_sum("............*...\n.............975"),
+                engine_schematic_sum(&Day3::parse("............*...\n.............975").unwrap()),
                 975
             );
         }
 
         #[test]
         fn test_engine_schematic_sum5() {
-            assert_eq!(engine_schematic_sum("975*............"), 975);
+            assert_eq!(
+                engine_schematic_sum(&Day3::parse("975*............").unwrap()),
+                975
+            );
         }
 
         #[test]
         fn test_engine_schematic_sum6() {
-            assert_eq!(engine_schematic_sum("*975............"), 975);
+            assert_eq!(
+                engine_schematic_sum(&Day3::parse("*975............").unwrap()),
+                975
+            );
         }
 
         #[test]
         fn test_engine_schematic_sum7() {
-            assert_eq!(engine_schematic_sum(".....+.58."), 0);
+            assert_eq!(engine_schematic_sum(&Day3::parse(".....+.58.").unwrap()), 0);
         }
 
         #[test]
         fn test_engine_schematic_sum8() {
-            assert_eq!(engine_schematic_sum("....694@980..."), 1674);
+            assert_eq!(
+                engine_schematic_sum(&Day3::parse("....694@980...").unwrap()),
+                1674
+            );
         }
 
         #[test]
         fn test_engine_schematic_sum9() {
-            assert_eq!(engine_schematic_sum("........\n.24..4..\n......*."), 4);
+            assert_eq!(
+                engine_schematic_sum(&Day3::parse("........\n.24..4..\n......*.").unwrap()),
+                4
+            );
         }
 
         #[test]
         fn test_engine_schematic_sum10() {
-            assert_eq!(engine_schematic_sum("........\n.34....*\n......24"), 24);
+            assert_eq!(
+                engine_schematic_sum(&Day3::parse("........\n.34....*\n......24").unwrap()),
+                24
+            );
         }
 
         #[test]
         fn test_engine_schematic_sum11() {
-            assert_eq!(engine_schematic_sum("*.......\n.34....."), 34);
+            assert_eq!(
+                engine_schematic_sum(&Day3::parse("*.......\n.34.....").unwrap()),
+                34
+            );
         }
 
         #[test]
         fn test_engine_schematic_sum12() {
-            assert_eq!(engine_schematic_sum("........\n.+34....."), 34);
+            assert_eq!(
+                engine_schematic_sum(&Day3::parse("........\n.+34.....").unwrap()),
+                34
+            );
         }
 
         #[test]
         fn test_engine_schematic_sum13() {
-            assert_eq!(engine_schematic_sum("........\n.-34....."), 34);
+            assert_eq!(
+                engine_schematic_sum(&Day3::parse("........\n.-34.....").unwrap()),
+                34
+            );
         }
 
         #[test]
         fn test_engine_schematic_sum14() {
-            assert_eq!(engine_schematic_sum("......34\n........."), 0);
+            assert_eq!(
+                engine_schematic_sum(&Day3::parse("......34\n.........").unwrap()),
+                0
+            );
         }
 
         #[test]
         fn test_engine_schematic_sum15() {
-            assert_eq!(engine_schematic_sum("12+.\n...."), 12);
+            assert_eq!(
+                engine_schematic_sum(&Day3::parse("12+.\n....").unwrap()),
+                12
+            );
         }
 
         #[test]
         fn test_engine_schematic_sum16() {
             assert_eq!(
-                engine_schematic_sum("$......$\n.1....1.\n.1....1.\n$......$"),
+                engine_schematic_sum(
+                    &Day3::parse("$......$\n.1....1.\n.1....1.\n$......$").unwrap()
+                ),
                 4
             );
         }
@@ -210,144 +349,112 @@ pub mod part1 {
         #[test]
         fn test_engine_schematic_sum17() {
             assert_eq!(
-                engine_schematic_sum("$..\n.11\n.11\n$..\n..$\n11.\n11.\n..$"),
+                engine_schematic_sum(
+                    &Day3::parse("$..\n.11\n.11\n$..\n..$\n11.\n11.\n..$").unwrap()
+                ),
                 44
             );
         }
 
         #[test]
         fn test_engine_schematic_sum18a() {
-            assert_eq!(engine_schematic_sum("12...\n..#.."), 12);
+            assert_eq!(
+                engine_schematic_sum(&Day3::parse("12...\n..#..").unwrap()),
+                12
+            );
         }
 
         #[test]
         fn test_engine_schematic_sum18b() {
-            assert_eq!(engine_schematic_sum("12...\n...#."), 0);
+            assert_eq!(
+                engine_schematic_sum(&Day3::parse("12...\n...#.").unwrap()),
+                0
+            );
         }
-    }
-}
-
-pub mod part2 {
-    use std::{collections::HashSet, fs};
-
-    fn is_digit(letter: char) -> bool {
-        matches!(
-            letter,
-            '0' | '1' | '2' | '3' | '4' | '5' | '6' | '7' | '8' | '9'
-        )
-    }
 
-    fn expand_digit(line: &str, start: usize) -> i32 {
-        let chars: Vec<char> = line.chars().collect();
-        let max = chars.len() - 1;
+        const EXAMPLE: &str = "467..114..\n...*......\n..35..633.\n......#...\n617*......\n.....+.58.\n..592.....\n......755.\n...$.*....\n.664.598..";
 
-        let mut result = String::new();
+        #[test]
+        fn test_parse_model() {
+            let schematic = Schematic::parse(EXAMPLE).unwrap();
 
-        if start >= max || !is_digit(chars[start]) {
-            return 0;
+            assert_eq!(schematic.numbers.len(), 10);
+            assert_eq!(
+                schematic.numbers[0],
+                PartNumber {
+                    value: 467,
+                    row: 0,
+                    columns: 0..3
+                }
+            );
+            assert_eq!(
+                schematic.symbols[0],
+                Symbol {
+                    glyph: '*',
+                    row: 1,
+                    column: 3
+                }
+            );
+            assert_eq!(schematic.neighbours_of_symbol(0), &[0, 2]);
+            assert_eq!(schematic.neighbours_of_number(1), &[] as &[usize]);
         }
 
-        for i in (0..start).rev() {
-            let letter = chars[i];
-            if !is_digit(letter) {
-                break;
-            }
+        #[test]
+        fn test_numbers_touching() {
+            let schematic = Schematic::parse(EXAMPLE).unwrap();
+            let values = |numbers: Vec<&PartNumber>| -> Vec<i32> {
+                numbers.iter().map(|number| number.value).collect()
+            };
 
-            result.insert(0, letter);
+            assert_eq!(values(schematic.numbers_touching('#')), vec![633]);
+            assert_eq!(
+                values(schematic.numbers_touching('*')),
+                vec![467, 35, 617, 755, 598]
+            );
+            assert_eq!(values(schematic.numbers_touching('%')), Vec::<i32>::new());
         }
 
-        for &letter in chars.iter().take(max + 1).skip(start) {
-            if !is_digit(letter) {
-                break;
-            }
+        #[test]
+        fn test_symbols_with_neighbours() {
+            let schematic = Schematic::parse(EXAMPLE).unwrap();
+            let glyphs = |count| -> String {
+                schematic
+                    .symbols_with_neighbours(count)
+                    .iter()
+                    .map(|symbol| symbol.glyph)
+                    .collect()
+            };
 
-            result.push(letter);
+            assert_eq!(glyphs(1), "#*+$");
+            assert_eq!(glyphs(2), "**");
+            assert_eq!(glyphs(3), "");
         }
 
-        match result.parse::<i32>() {
-            Ok(digit) => digit,
-            Err(_error) => 0,
-        }
-    }
+        #[test]
+        fn test_number_counted_once_per_symbol() {
+            let schematic = Schematic::parse("12.\n#*.").unwrap();
 
-    fn gear_ratios(schematic: &str) -> i32 {
-        let mut result: i32 = 0;
-        let lines: Vec<&str> = schematic.lines().collect();
-        let lines_max = (lines.len() - 1) as i32;
-
-        for (line_idx, line) in schematic.lines().enumerate() {
-            for (letter_idx, letter) in line.chars().enumerate() {
-                if letter == '*' {
-                    let prev_line_idx = (line_idx as i32) - 1;
-                    let next_line_idx = (line_idx as i32) + 1;
-
-                    let mut prev_line_digit_left = 0;
-                    let mut prev_line_digit_middle = 0;
-                    let mut prev_line_digit_right = 0;
-                    if prev_line_idx >= 0 {
-                        if letter_idx > 0 {
-                            prev_line_digit_left =
-                                expand_digit(lines[prev_line_idx as usize], letter_idx - 1);
-                        }
-                        prev_line_digit_middle =
-                            expand_digit(lines[prev_line_idx as usize], letter_idx);
-                        prev_line_digit_right =
-                            expand_digit(lines[prev_line_idx as usize], letter_idx + 1);
-                    }
+            assert_eq!(schematic.neighbours_of_number(0), &[0, 1]);
+            assert_eq!(schematic.neighbours_of_symbol(1), &[0]);
+            assert_eq!(schematic.part_numbers().count(), 1);
+        }
 
-                    let mut curr_line_digit_left = 0;
-                    if letter_idx > 0 {
-                        curr_line_digit_left = expand_digit(line, letter_idx - 1)
-                    }
-                    let curr_line_digit_right = expand_digit(line, letter_idx + 1);
-
-                    let mut next_line_digit_left = 0;
-                    let mut next_line_digit_middle = 0;
-                    let mut next_line_digit_right = 0;
-                    if next_line_idx <= lines_max {
-                        if letter_idx > 0 {
-                            next_line_digit_left =
-                                expand_digit(lines[next_line_idx as usize], letter_idx - 1);
-                        }
-                        next_line_digit_middle =
-                            expand_digit(lines[next_line_idx as usize], letter_idx);
-                        next_line_digit_right =
-                            expand_digit(lines[next_line_idx as usize], letter_idx + 1);
-                    }
+        #[test]
+        fn test_number_too_large() {
+            let error = Schematic::parse("..\n.*12345678901").err().unwrap();
 
-                    let unique = HashSet::from([
-                        prev_line_digit_left,
-                        prev_line_digit_middle,
-                        prev_line_digit_right,
-                        curr_line_digit_left,
-                        curr_line_digit_right,
-                        next_line_digit_left,
-                        next_line_digit_middle,
-                        next_line_digit_right,
-                    ]);
-
-                    let filtered: Vec<&i32> = unique.iter().filter(|&&digit| digit > 0).collect();
-
-                    if filtered.len() == 2 {
-                        let mut mult = 1;
-                        for digit in filtered {
-                            mult *= digit;
-                        }
-                        result += mult;
-                    }
-                }
-            }
+            assert_eq!((error.line, error.column), (2, 3));
+            assert_eq!(error.text, "12345678901");
+            assert!(Schematic::parse("*2147483647").is_ok());
         }
-
-        result
     }
+}
 
-    pub fn run() {
-        let contents = fs::read_to_string("inputs/day3.txt").expect("File not found");
-
-        let result = gear_ratios(&contents);
+pub mod part2 {
+    use super::*;
 
-        println!("Day 3 Part 2: {}", result);
+    pub fn gear_ratios(schematic: &Schematic) -> i32 {
+        schematic.gear_ratios().sum()
     }
 
     #[cfg(test)]
@@ -357,54 +464,78 @@ pub mod part2 {
         #[test]
         fn test_gear_ratio1() {
             assert_eq!(
-              gear_ratios("467..114..\n...*......\n..35..633.\n......#...\n617*......\n.....+.58.\n..592.....\n......755.\n...$.*....\n.664.598.."),
+              gear_ratios(&Day3::parse("467..114..\n...*......\n..35..633.\n......#...\n617*......\n.....+.58.\n..592.....\n......755.\n...$.*....\n.664.598..").unwrap()),
               467835
           );
         }
 
         #[test]
         fn test_gear_ratio2() {
-            assert_eq!(gear_ratios("....832*105..@.."), 87360);
+            assert_eq!(
+                gear_ratios(&Day3::parse("....832*105..@..").unwrap()),
+                87360
+            );
         }
 
         #[test]
         fn test_gear_ratio3() {
-            assert_eq!(gear_ratios("....832.105..@..\n.......*........"), 87360);
+            assert_eq!(
+                gear_ratios(&Day3::parse("....832.105..@..\n.......*........").unwrap()),
+                87360
+            );
         }
 
         #[test]
         fn test_gear_ratio4() {
-            assert_eq!(gear_ratios("......832....@..\n.......*........"), 0);
+            assert_eq!(
+                gear_ratios(&Day3::parse("......832....@..\n.......*........").unwrap()),
+                0
+            );
         }
 
         #[test]
         fn test_gear_ratio5() {
-            assert_eq!(gear_ratios("....832*.......\n........105..@."), 87360);
+            assert_eq!(
+                gear_ratios(&Day3::parse("....832*.......\n........105..@.").unwrap()),
+                87360
+            );
         }
 
         #[test]
         fn test_gear_ratio6() {
-            assert_eq!(gear_ratios("*832.......\n.105..@...."), 87360);
+            assert_eq!(
+                gear_ratios(&Day3::parse("*832.......\n.105..@....").unwrap()),
+                87360
+            );
         }
 
         #[test]
         fn test_gear_ratio7() {
-            assert_eq!(gear_ratios("......832*\n.......105"), 87360);
+            assert_eq!(
+                gear_ratios(&Day3::parse("......832*\n.......105").unwrap()),
+                87360
+            );
         }
 
         #[test]
         fn test_gear_ratio8() {
-            assert_eq!(gear_ratios(".......832\n......105*"), 87360);
+            assert_eq!(
+                gear_ratios(&Day3::parse(".......832\n......105*").unwrap()),
+                87360
+            );
         }
 
         #[test]
         fn test_gear_ratio9() {
-            assert_eq!(gear_ratios(".+.................\n...442...997..187..\n....*....*.......=.\n...963.926.39../...\n...........*....63.\n.....591...127....."), 1353821);
+            assert_eq!(gear_ratios(&Day3::parse(".+.................\n...442...997..187..\n....*....*.......=.\n...963.926.39../...\n...........*....63.\n.....591...127.....").unwrap()), 1353821);
         }
 
         #[test]
         fn test_gear_ratio10() {
-            assert_eq!(gear_ratios("...9...\n...*...\n...3..."), 27);
+            assert_eq!(
+                gear_ratios(&Day3::parse("...9...\n...*...\n...3...").unwrap()),
+                27
+            );
         }
     }
 }
@@ -1,28 +1,39 @@
+use crate::{
+    error::{number, parse_lines, ParseError},
+    solution::Solution,
+};
 use regex::Regex;
+use std::collections::HashMap;
 
-struct Card {
-    id: i32,
-    won_numbers: i32,
-    points: i32,
+pub struct Card {
+    pub id: i32,
+    pub won_numbers: i32,
+    pub points: i32,
 }
 
 impl Card {
-    fn new(line: &str) -> Self {
+    pub fn new(line: &str) -> Result<Self, ParseError> {
         let (_, [card_id, winning_numbers_str, my_numbers_str]) =
             Regex::new(r"^Card\s+([0-9]+):\s+(.*)\s+\|\s+(.*)$")
                 .unwrap()
                 .captures(line)
-                .expect("Failed to parse Card")
+                .ok_or_else(|| {
+                    ParseError::new(line, "expected `Card <id>: <numbers> | <numbers>`, found")
+                })?
                 .extract();
 
         let my_numbers: Vec<i32> = my_numbers_str
             .split_whitespace()
-            .map(|number| number.parse::<i32>().unwrap())
-            .collect();
+            .map(number)
+            .collect::<Result<_, _>>()?;
 
-        let won_numbers: Vec<i32> = winning_numbers_str
+        let winning_numbers: Vec<i32> = winning_numbers_str
             .split_whitespace()
-            .map(|number| number.parse::<i32>().unwrap())
+            .map(number)
+            .collect::<Result<_, _>>()?;
+
+        let won_numbers: Vec<i32> = winning_numbers
+            .into_iter()
             .filter(|&winning_number| {
                 my_numbers
                     .clone()
@@ -35,70 +46,96 @@ impl Card {
             .iter()
             .fold(0, |acc, _| if acc == 0 { 1 } else { acc * 2 });
 
-        Self {
-            id: card_id.parse::<i32>().unwrap(),
+        Ok(Self {
+            id: number(card_id)?,
             won_numbers: won_numbers.len() as i32,
             points,
-        }
+        })
     }
 }
 
-struct Pile {
-    cards: Vec<Card>,
+pub struct Pile {
+    pub cards: Vec<Card>,
 }
 
 impl Pile {
-    fn process_cards(&self) -> i32 {
-        let mut result = 0;
+    pub fn parse(contents: &str) -> Result<Self, ParseError> {
+        Ok(Self {
+            cards: parse_lines(contents, Card::new)?,
+        })
+    }
+
+    // How many copies of each card end up held, in pile order. Every copy of a card wins one
+    // copy of each of the next `won_numbers` cards, and won cards always come later in the pile,
+    // so a single pass settles the count of every card before it hands out copies.
+    pub fn copies(&self) -> Vec<(i32, i32)> {
+        let positions: HashMap<i32, usize> = self
+            .cards
+            .iter()
+            .enumerate()
+            .map(|(position, card)| (card.id, position))
+            .collect();
+        let mut copies: Vec<i32> = vec![1; self.cards.len()];
 
-        for card in &self.cards {
-            // Add one for the cards we had in the beginning.
-            result += 1 + self.process_card(card);
+        for (position, card) in self.cards.iter().enumerate() {
+            for id in card.id + 1..=card.id + card.won_numbers {
+                if let Some(&won) = positions.get(&id) {
+                    copies[won] += copies[position];
+                }
+            }
         }
 
-        result
+        self.cards.iter().map(|card| card.id).zip(copies).collect()
     }
 
-    fn process_card(&self, card: &Card) -> i32 {
-        let won_numbers = card.won_numbers;
-
-        if won_numbers > 0 {
-            let mut result: i32 = won_numbers;
-            let start = card.id + 1;
-            let end = start + won_numbers;
-            for i in start..end {
-                let other_card = self.cards.iter().find(|card| card.id == i).unwrap();
-                result += self.process_card(other_card);
-            }
+    pub fn process_cards(&self) -> i32 {
+        self.copies().iter().map(|(_, count)| count).sum()
+    }
 
-            return result;
-        }
+    pub fn copy_report(&self) -> String {
+        let copies = self.copies();
+        let mut report: String = copies
+            .iter()
+            .map(|(id, count)| format!("Card {}: {}\n", id, count))
+            .collect();
 
-        won_numbers
+        report.push_str(&format!(
+            "Total: {}\n",
+            copies.iter().map(|(_, count)| count).sum::<i32>()
+        ));
+        report
     }
 }
 
-pub mod part1 {
-    use std::fs;
+pub struct Day4;
 
-    use super::Card;
+impl Solution for Day4 {
+    type Input = Pile;
+    type Answer1 = i32;
+    type Answer2 = i32;
 
-    fn total_points(cards: &str) -> i32 {
-        let mut result: i32 = 0;
-        for line in cards.lines() {
-            let card = Card::new(line);
-            result += card.points;
-        }
+    fn parse(contents: &str) -> Result<Self::Input, ParseError> {
+        Pile::parse(contents)
+    }
 
-        result
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1::total_points(input)
     }
 
-    pub fn run() {
-        let contents = fs::read_to_string("inputs/day4.txt").expect("File not found");
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2::total_cards(input)
+    }
 
-        let result = total_points(&contents);
+    fn report(input: &Self::Input) -> Option<String> {
+        Some(input.copy_report())
+    }
+}
 
-        println!("Day 4 Part 1: {}", result);
+pub mod part1 {
+    use super::*;
+
+    pub fn total_points(pile: &Pile) -> i32 {
+        pile.cards.iter().map(|card| card.points).sum()
     }
 
     #[cfg(test)]
@@ -108,35 +145,29 @@ pub mod part1 {
         #[test]
         fn test_card_points() {
             assert_eq!(
-              total_points("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53\nCard 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19\nCard 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1\nCard 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83\nCard 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36\nCard 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11"),
+              total_points(&Day4::parse("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53\nCard 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19\nCard 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1\nCard 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83\nCard 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36\nCard 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11").unwrap()),
                 13
             );
         }
-    }
-}
-
-pub mod part2 {
-    use std::{fs, vec};
 
-    use super::{Card, Pile};
+        #[test]
+        fn test_card_parse_error() {
+            let contents = "Card 1: 41 48 | 83 86\nCard 2: 13 3x | 61 30";
+            let error = Day4::parse(contents).err().unwrap();
 
-    fn total_cards(contents: &str) -> i32 {
-        let mut cards: Vec<Card> = vec![];
-        for line in contents.lines() {
-            cards.push(Card::new(line));
+            assert_eq!(
+                (error.line, error.column, error.text.as_str()),
+                (2, 12, "3x")
+            );
         }
-
-        let pile = Pile { cards };
-
-        pile.process_cards()
     }
+}
 
-    pub fn run() {
-        let contents = fs::read_to_string("inputs/day4.txt").expect("File not found");
-
-        let result = total_cards(&contents);
+pub mod part2 {
+    use super::*;
 
-        println!("Day 4 Part 2: {}", result);
+    pub fn total_cards(pile: &Pile) -> i32 {
+        pile.process_cards()
     }
 
     #[cfg(test)]
@@ -146,9 +177,34 @@ pub mod part2 {
         #[test]
         fn test_total_cards() {
             assert_eq!(
-              total_cards("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53\nCard 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19\nCard 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1\nCard 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83\nCard 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36\nCard 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11"),
+              total_cards(&Day4::parse("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53\nCard 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19\nCard 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1\nCard 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83\nCard 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36\nCard 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11").unwrap()),
                 30
             );
         }
+
+        #[test]
+        fn test_copies() {
+            let pile = Day4::parse("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53\nCard 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19\nCard 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1\nCard 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83\nCard 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36\nCard 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11").unwrap();
+
+            assert_eq!(
+                pile.copies(),
+                vec![(1, 1), (2, 2), (3, 4), (4, 8), (5, 14), (6, 1)]
+            );
+            assert!(pile.copy_report().ends_with("Card 6: 1\nTotal: 30\n"));
+        }
+
+        // Winning the next two cards makes the copies grow like Fibonacci numbers, far beyond what
+        // replaying every won copy could finish
+        #[test]
+        fn test_long_cascade() {
+            let contents: String = (1..=40)
+                .map(|id| {
+                    let won = if id < 40 { "1 2" } else { "3 4" };
+                    format!("Card {}: 1 2 | {}\n", id, won)
+                })
+                .collect();
+
+            assert_eq!(total_cards(&Day4::parse(&contents).unwrap()), 701408690);
+        }
     }
 }
@@ -1,15 +1,114 @@
-struct AlmanacEntry {
-    dest_start: u64,
-    source_start: u64,
-    length: u64,
+use crate::{
+    error::{number, split_once, ParseError},
+    solution::{Optional, Solution},
+};
+use std::fmt::Display;
+
+pub struct AlmanacEntry {
+    pub dest_start: u64,
+    pub source_start: u64,
+    pub length: u64,
 }
 
-fn find_section(contents: &str, label: &str) -> Vec<AlmanacEntry> {
+// A half-open range of values, start..end
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Hash, Ord, PartialOrd)]
+pub struct Interval {
+    pub start: u64,
+    pub end: u64,
+}
+
+impl Interval {
+    pub fn new(start: u64, end: u64) -> Self {
+        Self { start, end }
+    }
+
+    // None when the range would run past u64::MAX
+    pub fn from_length(start: u64, length: u64) -> Option<Self> {
+        start.checked_add(length).map(|end| Self::new(start, end))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start >= self.end
+    }
+
+    pub fn contains(&self, value: u64) -> bool {
+        self.start <= value && value < self.end
+    }
+
+    pub fn intersect(&self, other: &Interval) -> Option<Interval> {
+        let overlap = Interval::new(self.start.max(other.start), self.end.min(other.end));
+
+        (!overlap.is_empty()).then_some(overlap)
+    }
+
+    // The parts below `at` and from `at` onwards, either of which may be missing
+    pub fn split_at(&self, at: u64) -> (Option<Interval>, Option<Interval>) {
+        let below = Interval::new(self.start, self.end.min(at));
+        let above = Interval::new(self.start.max(at), self.end);
+
+        (
+            (!below.is_empty()).then_some(below),
+            (!above.is_empty()).then_some(above),
+        )
+    }
+
+    pub fn shift(&self, offset: i64) -> Interval {
+        Interval::new(
+            self.start.wrapping_add_signed(offset),
+            self.end.wrapping_add_signed(offset),
+        )
+    }
+}
+
+impl AlmanacEntry {
+    pub fn parse(line: &str) -> Result<Self, ParseError> {
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        let [dest_start, source_start, length] = parts[..] else {
+            return Err(ParseError::new(
+                line,
+                "expected `<dest> <source> <length>`, found",
+            ));
+        };
+
+        let entry = Self {
+            dest_start: number(dest_start)?,
+            source_start: number(source_start)?,
+            length: number(length)?,
+        };
+
+        let ends =
+            [entry.dest_start, entry.source_start].map(|start| start.checked_add(entry.length));
+        if ends.contains(&None) {
+            return Err(ParseError::new(
+                line,
+                "expected ranges that end below 2^64, found",
+            ));
+        }
+
+        Ok(entry)
+    }
+
+    pub fn source_range(&self) -> Interval {
+        Interval::from_length(self.source_start, self.length)
+            .expect("Ranges are checked by AlmanacEntry::parse")
+    }
+
+    pub fn dest_range(&self) -> Interval {
+        Interval::from_length(self.dest_start, self.length)
+            .expect("Ranges are checked by AlmanacEntry::parse")
+    }
+
+    pub fn offset(&self) -> i64 {
+        self.dest_start as i64 - self.source_start as i64
+    }
+}
+
+pub fn find_section(contents: &str, label: &str) -> Result<Vec<AlmanacEntry>, ParseError> {
     let mut start_idx: Option<usize> = None;
     let mut end_idx: Option<usize> = None;
 
     let lines = contents.lines();
-    let max = lines.clone().count() - 1;
+    let max = lines.clone().count().saturating_sub(1);
     let first_line = format!("{} map:", label);
     for (i, line) in lines.enumerate() {
         if line == first_line {
@@ -26,162 +125,464 @@ fn find_section(contents: &str, label: &str) -> Vec<AlmanacEntry> {
     match (start_idx, end_idx) {
         (Some(start), Some(end)) => contents
             .lines()
+            .enumerate()
             .skip(start)
             .take(end - start)
-            .map(|s| {
-                let parts: Vec<&str> = s.split_whitespace().collect();
-                let dest_start = parts[0].parse::<u64>().unwrap();
-                let source_start = parts[1].parse::<u64>().unwrap();
-                let length = parts[2].parse::<u64>().unwrap();
-
-                AlmanacEntry {
-                    dest_start,
-                    source_start,
-                    length,
-                }
+            .map(|(index, line)| {
+                AlmanacEntry::parse(line).map_err(|error| error.on_line(index, line))
             })
             .collect(),
-        _ => vec![],
+        _ => Err(ParseError::new(&first_line, "missing section")),
     }
 }
 
-fn find_dest(section: &Vec<AlmanacEntry>, source: u64) -> u64 {
+// Both lookups treat entries as half-open ranges, so they agree at every boundary
+pub fn find_dest(section: &[AlmanacEntry], source: u64) -> u64 {
+    section
+        .iter()
+        .find(|entry| entry.source_range().contains(source))
+        .map_or(source, |entry| source.wrapping_add_signed(entry.offset()))
+}
+
+pub fn find_source(section: &[AlmanacEntry], dest: u64) -> u64 {
+    section
+        .iter()
+        .find(|entry| entry.dest_range().contains(dest))
+        .map_or(dest, |entry| dest.wrapping_add_signed(-entry.offset()))
+}
+
+// Pushes whole ranges through a section. Every piece covered by an entry is shifted to its
+// destination, whatever no entry covers keeps its value.
+pub fn map_ranges(section: &[AlmanacEntry], ranges: &[Interval]) -> Vec<Interval> {
+    let mut mapped: Vec<Interval> = vec![];
+    let mut pending: Vec<Interval> = ranges.to_vec();
+
     for entry in section {
-        if source < entry.source_start || source > entry.source_start + entry.length {
-            continue;
+        let source = entry.source_range();
+        let mut unmapped: Vec<Interval> = vec![];
+
+        for range in pending {
+            if let Some(overlap) = range.intersect(&source) {
+                mapped.push(overlap.shift(entry.offset()));
+            }
+
+            let (below, rest) = range.split_at(source.start);
+            let (_, above) = rest.map_or((None, None), |rest| rest.split_at(source.end));
+            unmapped.extend(below.into_iter().chain(above));
         }
 
-        return entry.dest_start + source - entry.source_start;
+        pending = unmapped;
     }
 
-    source
+    mapped.extend(pending);
+    mapped
 }
 
-fn find_source(section: &Vec<AlmanacEntry>, dest: u64) -> u64 {
-    for entry in section {
-        if dest < entry.dest_start || dest >= entry.dest_start + entry.length {
-            continue;
-        }
+// A stretch of source values that all move by the same offset
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub struct Piece {
+    pub source: Interval,
+    pub offset: i64,
+}
 
-        return entry.source_start + dest - entry.dest_start;
+impl Piece {
+    pub fn destination(&self) -> Interval {
+        self.source.shift(self.offset)
     }
+}
 
-    dest
+// A piecewise-linear map over every u64 value. The pieces are sorted by source, do not overlap
+// and leave no gaps, so values no section entry covers are explicit identity pieces.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct PiecewiseMap {
+    pieces: Vec<Piece>,
 }
 
-pub mod part1 {
-    use super::*;
-    use std::fs;
-
-    fn seed_locations(contents: &str) -> u64 {
-        let seeds_line: Vec<&str> = contents.lines().take(1).collect();
-        let seeds_part: Vec<&str> = seeds_line[0].split(": ").collect();
-        let seeds: Vec<u64> = seeds_part[1]
-            .split_whitespace()
-            .map(|seed| seed.parse::<u64>().unwrap())
+impl PiecewiseMap {
+    pub fn identity() -> Self {
+        Self {
+            pieces: vec![Piece {
+                source: Interval::new(0, u64::MAX),
+                offset: 0,
+            }],
+        }
+    }
+
+    pub fn from_section(section: &[AlmanacEntry]) -> Self {
+        let mut entries: Vec<Piece> = section
+            .iter()
+            .map(|entry| Piece {
+                source: entry.source_range(),
+                offset: entry.offset(),
+            })
             .collect();
+        entries.sort_by_key(|piece| piece.source.start);
+
+        let mut pieces: Vec<Piece> = vec![];
+        let mut cursor = 0;
+        for entry in entries {
+            // Entries are not supposed to overlap, clip them just in case
+            let source = Interval::new(entry.source.start.max(cursor), entry.source.end);
+            if source.is_empty() {
+                continue;
+            }
+
+            if source.start > cursor {
+                pieces.push(Piece {
+                    source: Interval::new(cursor, source.start),
+                    offset: 0,
+                });
+            }
+
+            pieces.push(Piece { source, ..entry });
+            cursor = source.end;
+        }
+
+        if cursor < u64::MAX {
+            pieces.push(Piece {
+                source: Interval::new(cursor, u64::MAX),
+                offset: 0,
+            });
+        }
+
+        Self::merged(pieces)
+    }
+
+    // Joins neighbouring pieces that move by the same offset
+    fn merged(pieces: Vec<Piece>) -> Self {
+        let mut merged: Vec<Piece> = vec![];
+
+        for piece in pieces {
+            match merged.last_mut() {
+                Some(last)
+                    if last.offset == piece.offset && last.source.end == piece.source.start =>
+                {
+                    last.source.end = piece.source.end
+                }
+                _ => merged.push(piece),
+            }
+        }
+
+        Self { pieces: merged }
+    }
+
+    pub fn pieces(&self) -> &[Piece] {
+        &self.pieces
+    }
+
+    // Index of the piece containing `value`
+    fn piece_index(&self, value: u64) -> usize {
+        self.pieces
+            .partition_point(|piece| piece.source.end <= value)
+    }
+
+    pub fn lookup(&self, value: u64) -> u64 {
+        match self.pieces.get(self.piece_index(value)) {
+            Some(piece) if piece.source.start <= value => value.wrapping_add_signed(piece.offset),
+            _ => value,
+        }
+    }
+
+    // Applies `self` first and `next` to its result
+    pub fn then(&self, next: &PiecewiseMap) -> PiecewiseMap {
+        let mut pieces: Vec<Piece> = vec![];
 
-        let seed_to_soil = &find_section(contents, "seed-to-soil");
-        let soil_to_fertilizer = &find_section(contents, "soil-to-fertilizer");
-        let fertilizer_to_water = &find_section(contents, "fertilizer-to-water");
-        let water_to_light = &find_section(contents, "water-to-light");
-        let light_to_tempature = &find_section(contents, "light-to-temperature");
-        let tempature_to_humidity = &find_section(contents, "temperature-to-humidity");
-        let humidity_to_location = &find_section(contents, "humidity-to-location");
-
-        let mut min_location = std::u64::MAX;
-        let mut min_seed: u64 = 0;
-        for seed in seeds {
-            let soil = find_dest(seed_to_soil, seed);
-            let fertilizer = find_dest(soil_to_fertilizer, soil);
-            let water = find_dest(fertilizer_to_water, fertilizer);
-            let light = find_dest(water_to_light, water);
-            let tempature = find_dest(light_to_tempature, light);
-            let humidity = find_dest(tempature_to_humidity, tempature);
-            let location = find_dest(humidity_to_location, humidity);
-
-            if location < min_location {
-                min_location = location;
-                min_seed = seed;
+        for piece in &self.pieces {
+            let image = piece.destination();
+
+            for next_piece in &next.pieces[next.piece_index(image.start)..] {
+                let Some(overlap) = image.intersect(&next_piece.source) else {
+                    break;
+                };
+
+                pieces.push(Piece {
+                    source: overlap.shift(-piece.offset),
+                    offset: piece.offset + next_piece.offset,
+                });
             }
         }
 
-        println!("location = {}, seed = {}", min_location, min_seed);
-        min_location
+        pieces.sort_by_key(|piece| piece.source.start);
+        Self::merged(pieces)
     }
 
-    pub fn run() {
-        let contents = fs::read_to_string("inputs/day5.txt").expect("File not found");
+    // The map running from destination back to source. Only a map that sends every value to a
+    // different one can be inverted, anything else returns None.
+    pub fn inverse(&self) -> Option<PiecewiseMap> {
+        let mut pieces: Vec<Piece> = self
+            .pieces
+            .iter()
+            .map(|piece| Piece {
+                source: piece.destination(),
+                offset: -piece.offset,
+            })
+            .collect();
+        pieces.sort_by_key(|piece| piece.source.start);
+
+        let covers = pieces.first().map(|piece| piece.source.start) == Some(0)
+            && pieces.last().map(|piece| piece.source.end) == Some(u64::MAX)
+            && pieces
+                .windows(2)
+                .all(|pair| pair[0].source.end == pair[1].source.start);
 
-        let result = seed_locations(&contents);
+        covers.then(|| Self::merged(pieces))
+    }
+}
 
-        println!("Day 5 Part 1: {}", result);
+impl Display for PiecewiseMap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let range = |interval: Interval| match interval.end {
+            u64::MAX => format!("{}..", interval.start),
+            end => format!("{}..{}", interval.start, end),
+        };
+
+        writeln!(
+            f,
+            "{:>24} | {:>24} | {:>12}",
+            "Source", "Destination", "Offset"
+        )?;
+        for piece in &self.pieces {
+            writeln!(
+                f,
+                "{:>24} | {:>24} | {:>+12}",
+                range(piece.source),
+                range(piece.destination()),
+                piece.offset
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+const SECTIONS: [&str; 7] = [
+    "seed-to-soil",
+    "soil-to-fertilizer",
+    "fertilizer-to-water",
+    "water-to-light",
+    "light-to-temperature",
+    "temperature-to-humidity",
+    "humidity-to-location",
+];
+
+pub struct Almanac {
+    pub seeds: Vec<u64>,
+    pub sections: Vec<Vec<AlmanacEntry>>,
+}
+
+impl Almanac {
+    pub fn parse(contents: &str) -> Result<Self, ParseError> {
+        let seeds_line = contents.lines().next().unwrap_or_default();
+        let seeds: Vec<u64> = split_once(seeds_line, ": ")
+            .and_then(|(_, seeds)| seeds.split_whitespace().map(number).collect())
+            .map_err(|error| error.on_line(0, seeds_line))?;
+
+        Ok(Self {
+            seeds,
+            sections: SECTIONS
+                .iter()
+                .map(|label| find_section(contents, label))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    // The whole seed to location chain collapsed into a single map
+    pub fn composed(&self) -> PiecewiseMap {
+        self.sections
+            .iter()
+            .fold(PiecewiseMap::identity(), |map, section| {
+                map.then(&PiecewiseMap::from_section(section))
+            })
+    }
+
+    // In part 2 the seeds line lists pairs of range start and length. A range running past the
+    // largest number is cut off there, as no seed could be numbered beyond it.
+    pub fn seed_ranges(&self) -> Vec<Interval> {
+        self.seeds
+            .chunks_exact(2)
+            .map(|pair| {
+                Interval::from_length(pair[0], pair[1]).unwrap_or(Interval::new(pair[0], u64::MAX))
+            })
+            .collect()
+    }
+
+    // Every location reachable from `seeds`, as ranges
+    pub fn location_ranges(&self, seeds: &[Interval]) -> Vec<Interval> {
+        self.sections
+            .iter()
+            .fold(seeds.to_vec(), |ranges, section| {
+                map_ranges(section, &ranges)
+            })
+    }
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = Almanac;
+    type Answer1 = Optional<u64>;
+    type Answer2 = Optional<u64>;
+
+    fn parse(contents: &str) -> Result<Self::Input, ParseError> {
+        Almanac::parse(contents)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        Optional(part1::seed_locations(input))
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        Optional(part2::seed_range_locations(input))
+    }
+
+    fn report(input: &Self::Input) -> Option<String> {
+        Some(input.composed().to_string())
+    }
+}
+
+pub mod part1 {
+    use super::*;
+
+    // The lowest location of any seed, or None if there are no seeds
+    pub fn seed_locations(almanac: &Almanac) -> Option<u64> {
+        let chain = almanac.composed();
+
+        almanac.seeds.iter().map(|&seed| chain.lookup(seed)).min()
     }
 
     #[cfg(test)]
     mod tests {
         use super::*;
 
+        const EXAMPLE: &str = "seeds: 79 14 55 13\n\nseed-to-soil map:\n50 98 2\n52 50 48\n\nsoil-to-fertilizer map:\n0 15 37\n37 52 2\n39 0 15\n\nfertilizer-to-water map:\n49 53 8\n0 11 42\n42 0 7\n57 7 4\n\nwater-to-light map:\n88 18 7\n18 25 70\n\nlight-to-temperature map:\n45 77 23\n81 45 19\n68 64 13\n\ntemperature-to-humidity map:\n0 69 1\n1 0 69\n\nhumidity-to-location map:\n60 56 37\n56 93 4";
+
         #[test]
         fn test_seed_locations() {
-            assert_eq!(seed_locations("seeds: 79 14 55 13\n\nseed-to-soil map:\n50 98 2\n52 50 48\n\nsoil-to-fertilizer map:\n0 15 37\n37 52 2\n39 0 15\n\nfertilizer-to-water map:\n49 53 8\n0 11 42\n42 0 7\n57 7 4\n\nwater-to-light map:\n88 18 7\n18 25 70\n\nlight-to-temperature map:\n45 77 23\n81 45 19\n68 64 13\n\ntemperature-to-humidity map:\n0 69 1\n1 0 69\n\nhumidity-to-location map:\n60 56 37\n56 93 4"), 35);
+            assert_eq!(seed_locations(&Day5::parse(EXAMPLE).unwrap()), Some(35));
         }
-    }
-}
-pub mod part2 {
-    use super::*;
-    use std::{fs, vec};
-
-    fn seed_range_locations(contents: &str) -> u64 {
-        let seeds_line: Vec<&str> = contents.lines().take(1).collect();
-        let seeds_part: Vec<&str> = seeds_line[0].split(": ").collect();
-        let seed_ranges: Vec<u64> = seeds_part[1]
-            .split_whitespace()
-            .map(|seed| seed.parse::<u64>().unwrap())
-            .collect();
 
-        let mut seed_tuples: Vec<(u64, u64)> = vec![];
-
-        for i in (0..seed_ranges.len()).step_by(2) {
-            seed_tuples.push((seed_ranges[i], seed_ranges[i + 1]))
-        }
-
-        let seed_to_soil = &find_section(contents, "seed-to-soil");
-        let soil_to_fertilizer = &find_section(contents, "soil-to-fertilizer");
-        let fertilizer_to_water = &find_section(contents, "fertilizer-to-water");
-        let water_to_light = &find_section(contents, "water-to-light");
-        let light_to_tempature = &find_section(contents, "light-to-temperature");
-        let tempature_to_humidity = &find_section(contents, "temperature-to-humidity");
-        let humidity_to_location = &find_section(contents, "humidity-to-location");
-
-        let mut location: u64 = 0;
-        loop {
-            let humidity = find_source(humidity_to_location, location);
-            let tempature = find_source(tempature_to_humidity, humidity);
-            let light = find_source(light_to_tempature, tempature);
-            let water = find_source(water_to_light, light);
-            let fertilizer = find_source(fertilizer_to_water, water);
-            let soil = find_source(soil_to_fertilizer, fertilizer);
-            let seed = find_source(seed_to_soil, soil);
-
-            for (start, length) in &seed_tuples {
-                let range_start = *start;
-                let range_end = range_start + *length;
-                if seed >= range_start && seed < range_end {
-                    return location;
-                }
+        #[test]
+        fn test_composed_map() {
+            let almanac = Day5::parse(EXAMPLE).unwrap();
+            let chain = almanac.composed();
+
+            let locations: Vec<u64> = [79, 14, 55, 13]
+                .iter()
+                .map(|&seed| chain.lookup(seed))
+                .collect();
+            assert_eq!(locations, vec![82, 43, 86, 35]);
+
+            for seed in 0..200 {
+                let stepwise = almanac
+                    .sections
+                    .iter()
+                    .fold(seed, |value, section| find_dest(section, value));
+                assert_eq!(chain.lookup(seed), stepwise);
+            }
+        }
+
+        #[test]
+        fn test_composed_inverse() {
+            let chain = Day5::parse(EXAMPLE).unwrap().composed();
+            let inverse = chain.inverse().unwrap();
+
+            for seed in 0..200 {
+                assert_eq!(inverse.lookup(chain.lookup(seed)), seed);
             }
 
-            location += 1;
+            let squashed = PiecewiseMap::from_section(&[AlmanacEntry {
+                dest_start: 0,
+                source_start: 10,
+                length: 5,
+            }]);
+            assert_eq!(squashed.inverse(), None);
         }
-    }
 
-    pub fn run() {
-        let contents = fs::read_to_string("inputs/day5.txt").expect("File not found");
+        #[test]
+        fn test_composed_table() {
+            let map = PiecewiseMap::from_section(&[
+                AlmanacEntry {
+                    dest_start: 50,
+                    source_start: 98,
+                    length: 2,
+                },
+                AlmanacEntry {
+                    dest_start: 52,
+                    source_start: 50,
+                    length: 48,
+                },
+            ]);
+
+            assert_eq!(map.pieces().len(), 4);
+            assert_eq!(
+                map.to_string().lines().nth(2).unwrap(),
+                "                  50..98 |                  52..100 |           +2"
+            );
+        }
+
+        #[test]
+        fn test_report() {
+            let almanac = Day5::parse(EXAMPLE).unwrap();
+
+            assert_eq!(Day5::report(&almanac), Some(almanac.composed().to_string()));
+        }
 
-        let result = seed_range_locations(&contents);
+        #[test]
+        fn test_find_dest_range_ends() {
+            let section = &Day5::parse(EXAMPLE).unwrap().sections[0];
+
+            assert_eq!(find_dest(section, 97), 99);
+            assert_eq!(find_dest(section, 98), 50);
+            assert_eq!(find_dest(section, 99), 51);
+            assert_eq!(find_dest(section, 100), 100);
+            assert_eq!(find_source(section, 52), 50);
+            assert_eq!(find_source(section, 100), 100);
+        }
+
+        #[test]
+        fn test_round_trip_at_every_boundary() {
+            let almanac = Day5::parse(EXAMPLE).unwrap();
+
+            for section in &almanac.sections {
+                for entry in section {
+                    let boundaries = [entry.source_range(), entry.dest_range()]
+                        .into_iter()
+                        .flat_map(|range| [range.start, range.end])
+                        .flat_map(|edge| [edge.saturating_sub(1), edge, edge + 1]);
+
+                    for x in boundaries {
+                        assert_eq!(find_source(section, find_dest(section, x)), x);
+                        assert_eq!(find_dest(section, find_source(section, x)), x);
+                    }
+                }
+            }
+        }
+
+        #[test]
+        fn test_almanac_parse_errors() {
+            let error = Day5::parse("seeds: 79 14\n\nseed-to-soil map:\n50 98")
+                .err()
+                .unwrap();
+            assert_eq!(error.text, "50 98");
+
+            let error = Day5::parse("seeds: 79 14\n\nseed-to-soil map:\n50 98 2")
+                .err()
+                .unwrap();
+            assert_eq!(error.text, "soil-to-fertilizer map:");
+        }
+    }
+}
+pub mod part2 {
+    use super::*;
 
-        println!("Day 5 Part 2: {}", result);
+    // The lowest location of any seed in the ranges, or None if every range is empty
+    pub fn seed_range_locations(almanac: &Almanac) -> Option<u64> {
+        almanac
+            .location_ranges(&almanac.seed_ranges())
+            .iter()
+            .filter(|range| !range.is_empty())
+            .map(|range| range.start)
+            .min()
     }
 
     #[cfg(test)]
@@ -190,7 +591,93 @@ pub mod part2 {
 
         #[test]
         fn test_seed_range_locations() {
-            assert_eq!(seed_range_locations("seeds: 79 14 55 13\n\nseed-to-soil map:\n50 98 2\n52 50 48\n\nsoil-to-fertilizer map:\n0 15 37\n37 52 2\n39 0 15\n\nfertilizer-to-water map:\n49 53 8\n0 11 42\n42 0 7\n57 7 4\n\nwater-to-light map:\n88 18 7\n18 25 70\n\nlight-to-temperature map:\n45 77 23\n81 45 19\n68 64 13\n\ntemperature-to-humidity map:\n0 69 1\n1 0 69\n\nhumidity-to-location map:\n60 56 37\n56 93 4"), 46);
+            assert_eq!(seed_range_locations(&Day5::parse("seeds: 79 14 55 13\n\nseed-to-soil map:\n50 98 2\n52 50 48\n\nsoil-to-fertilizer map:\n0 15 37\n37 52 2\n39 0 15\n\nfertilizer-to-water map:\n49 53 8\n0 11 42\n42 0 7\n57 7 4\n\nwater-to-light map:\n88 18 7\n18 25 70\n\nlight-to-temperature map:\n45 77 23\n81 45 19\n68 64 13\n\ntemperature-to-humidity map:\n0 69 1\n1 0 69\n\nhumidity-to-location map:\n60 56 37\n56 93 4").unwrap()), Some(46));
+        }
+
+        #[test]
+        fn test_without_seeds() {
+            let sections: String = SECTIONS
+                .iter()
+                .map(|label| format!("\n\n{} map:\n0 0 1", label))
+                .collect();
+
+            for seeds in ["seeds: ", "seeds: 5 0"] {
+                let almanac = Day5::parse(&format!("{}{}", seeds, sections)).unwrap();
+
+                assert_eq!(seed_range_locations(&almanac), None);
+                assert_eq!(Day5::part2(&almanac).to_string(), "none");
+            }
+            assert_eq!(
+                super::super::part1::seed_locations(
+                    &Day5::parse(&format!("seeds: {}", sections)).unwrap()
+                ),
+                None
+            );
+        }
+
+        #[test]
+        fn test_ranges_past_u64() {
+            let sections: String = SECTIONS
+                .iter()
+                .map(|label| format!("\n\n{} map:\n0 0 1", label))
+                .collect();
+            let almanac =
+                Day5::parse(&format!("seeds: 18446744073709551614 5{}", sections)).unwrap();
+
+            assert_eq!(
+                almanac.seed_ranges(),
+                vec![Interval::new(18446744073709551614, u64::MAX)]
+            );
+            assert!(AlmanacEntry::parse("0 18446744073709551615 1").is_err());
+            assert_eq!(Interval::from_length(u64::MAX, 1), None);
+        }
+
+        #[test]
+        fn test_interval_operations() {
+            let range = Interval::new(10, 20);
+
+            assert_eq!(
+                range.intersect(&Interval::new(15, 30)),
+                Some(Interval::new(15, 20))
+            );
+            assert_eq!(range.intersect(&Interval::new(20, 30)), None);
+            assert_eq!(
+                range.split_at(12),
+                (Some(Interval::new(10, 12)), Some(Interval::new(12, 20)))
+            );
+            assert_eq!(range.split_at(5), (None, Some(range)));
+            assert_eq!(range.split_at(20), (Some(range), None));
+            assert_eq!(range.shift(-10), Interval::new(0, 10));
+        }
+
+        #[test]
+        fn test_map_ranges() {
+            let section = vec![
+                AlmanacEntry {
+                    dest_start: 50,
+                    source_start: 98,
+                    length: 2,
+                },
+                AlmanacEntry {
+                    dest_start: 52,
+                    source_start: 50,
+                    length: 48,
+                },
+            ];
+
+            let mut mapped =
+                map_ranges(&section, &[Interval::new(45, 100), Interval::new(120, 125)]);
+            mapped.sort();
+
+            assert_eq!(
+                mapped,
+                vec![
+                    Interval::new(45, 50),
+                    Interval::new(50, 52),
+                    Interval::new(52, 100),
+                    Interval::new(120, 125),
+                ]
+            );
         }
     }
 }
@@ -1,73 +1,145 @@
-fn calculate_distance(time: u64, hold: u64) -> u64 {
+use crate::{
+    error::{number, parse_lines, ParseError},
+    solution::Solution,
+};
+use num::{BigUint, CheckedSub, One, ToPrimitive, Zero};
+use regex::Regex;
+
+pub fn calculate_distance(time: u64, hold: u64) -> u64 {
     let time_left = time - hold;
     hold * time_left
 }
 
-fn find_roots(a: f64, b: f64, c: f64) -> Option<(f64, f64)> {
-    let discriminant = b * b - 4.0 * a * c;
-
-    if discriminant < 0.0 {
-        // No real roots
-        None
-    } else {
-        // Calculating the two roots
-        let root1 = (-b + discriminant.sqrt()) / (2.0 * a);
-        let root2 = (-b - discriminant.sqrt()) / (2.0 * a);
-        Some((root1, root2))
+// The first and last hold times that beat `best_distance`. Holding for `h` travels h * (time - h),
+// so a win means h² - time * h + best_distance < 0. The bounds start from an integer square root
+// and are nudged to the exact integers, so no floating point rounding is involved. Big integers
+// keep part 2's joined numbers exact however many digits they have.
+pub fn winning_hold_range(time: &BigUint, best_distance: &BigUint) -> Option<(BigUint, BigUint)> {
+    let discriminant = (time * time).checked_sub(&(best_distance * 4u32))?;
+    let beats = |hold: &BigUint| hold * (time - hold) > *best_distance;
+    let half = time / 2u32;
+
+    let mut low = (time - discriminant.sqrt()) / 2u32;
+    while low <= half && !beats(&low) {
+        low += 1u32;
     }
+    while !low.is_zero() && beats(&(&low - 1u32)) {
+        low -= 1u32;
+    }
+
+    // The distance peaks at time / 2, so a race not won by then cannot be won at all
+    if low > half {
+        return None;
+    }
+
+    let high = time - &low;
+    Some((low, high))
 }
 
-fn find_winning_count(time: u64, best_distance: u64) -> Option<u64> {
-    match find_roots(-1.0, time as f64, -1.0 * (best_distance as f64)) {
-        Some((root1, root2)) => {
-            let mut start = root1.ceil() as i64;
-            if start as f64 == root1 {
-                start += 1;
-            }
-            let mut end = root2.floor() as i64;
-            if end as f64 == root2 {
-                end -= 1;
-            }
+pub fn winning_holds(time: u64, best_distance: u64) -> Option<(u64, u64)> {
+    let (low, high) = winning_hold_range(&time.into(), &best_distance.into())?;
+    let hold = |hold: BigUint| hold.to_u64().expect("A hold never exceeds the race time");
+
+    Some((hold(low), hold(high)))
+}
 
-            Some((end - start + 1) as u64)
+pub fn find_winning_count(time: u64, best_distance: u64) -> Option<u64> {
+    winning_holds(time, best_distance).map(|(start, end)| end - start + 1)
+}
+
+#[derive(Debug)]
+pub struct Races {
+    pub times: Vec<u64>,
+    pub distances: Vec<u64>,
+}
+
+impl Races {
+    pub fn parse(contents: &str) -> Result<Self, ParseError> {
+        let lines_parsed: Vec<Vec<u64>> = parse_lines(contents, |line| {
+            let (_, [numbers]) = Regex::new(r"^.*: (.*)$")
+                .expect("Invalid regex")
+                .captures(line)
+                .ok_or_else(|| ParseError::new(line, "expected `<label>: <numbers>`, found"))?
+                .extract();
+
+            numbers.split_whitespace().map(number).collect()
+        })?;
+
+        let [times, distances] = &lines_parsed[..] else {
+            return Err(ParseError::new(
+                contents,
+                "expected a time line and a distance line, found",
+            ));
+        };
+
+        if times.len() != distances.len() {
+            let line = contents.lines().nth(1).unwrap_or_default();
+
+            return Err(ParseError::new(
+                line,
+                format!(
+                    "expected {} distances to match the times, found",
+                    times.len()
+                ),
+            )
+            .on_line(1, line));
         }
-        None => None,
+
+        Ok(Self {
+            times: times.clone(),
+            distances: distances.clone(),
+        })
+    }
+
+    pub fn games(&self) -> impl Iterator<Item = (u64, u64)> + '_ {
+        self.times
+            .iter()
+            .copied()
+            .zip(self.distances.iter().copied())
+    }
+
+    // Part 2 reads each line as a single number with the spaces removed, which can run past u64
+    pub fn single_race(&self) -> (BigUint, BigUint) {
+        let join = |numbers: &[u64]| {
+            numbers
+                .iter()
+                .map(|number| number.to_string())
+                .collect::<String>()
+                .parse()
+                .expect("Joined numbers are made of digits")
+        };
+
+        (join(&self.times), join(&self.distances))
     }
 }
 
-pub mod part1 {
-    use super::*;
-    use regex::Regex;
-    use std::fs;
+pub struct Day6;
 
-    fn calculate_winning_margin_brute_force(contents: &str) -> u64 {
-        let lines: Vec<&str> = contents.lines().collect();
+impl Solution for Day6 {
+    type Input = Races;
+    type Answer1 = u64;
+    type Answer2 = BigUint;
 
-        let lines_parsed: Vec<&str> = lines
-            .iter()
-            .map(|line| {
-                let (_, [numbers]) = Regex::new(r"^.*: (.*)$")
-                    .expect("Invalid regex")
-                    .captures(line)
-                    .expect("Failed to parse line")
-                    .extract();
-
-                numbers
-            })
-            .collect();
-
-        let games = lines_parsed[0]
-            .split_whitespace()
-            .map(|number| number.parse::<u64>().unwrap())
-            .zip(
-                lines_parsed[1]
-                    .split_whitespace()
-                    .map(|number| number.parse::<u64>().unwrap()),
-            );
+    fn parse(contents: &str) -> Result<Self::Input, ParseError> {
+        Races::parse(contents)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1::calculate_winning_margin_quadratic(input)
+    }
 
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2::calculate_winnings_quadratic(input)
+    }
+}
+
+pub mod part1 {
+    use super::*;
+
+    pub fn calculate_winning_margin_brute_force(races: &Races) -> u64 {
         let mut margin_of_error: Vec<u64> = Vec::new();
 
-        for (time, best_distance) in games {
+        for (time, best_distance) in races.games() {
             let mut count_winnable: u64 = 0;
 
             for t in 0..(time + 1) {
@@ -84,52 +156,16 @@ pub mod part1 {
         margin_of_error.iter().product()
     }
 
-    fn calculate_winning_margin_quadratic(contents: &str) -> u64 {
-        let lines: Vec<&str> = contents.lines().collect();
-
-        let lines_parsed: Vec<&str> = lines
-            .iter()
-            .map(|line| {
-                let (_, [numbers]) = Regex::new(r"^.*: (.*)$")
-                    .expect("Invalid regex")
-                    .captures(line)
-                    .expect("Failed to parse line")
-                    .extract();
-
-                numbers
-            })
-            .collect();
-
-        let games = lines_parsed[0]
-            .split_whitespace()
-            .map(|number| number.parse::<u64>().unwrap())
-            .zip(
-                lines_parsed[1]
-                    .split_whitespace()
-                    .map(|number| number.parse::<u64>().unwrap()),
-            );
-
+    pub fn calculate_winning_margin_quadratic(races: &Races) -> u64 {
         let mut margin_of_error: Vec<u64> = Vec::new();
 
-        for (time, best_distance) in games {
-            if let Some(won_games) = find_winning_count(time, best_distance) {
-                margin_of_error.push(won_games)
-            }
+        for (time, best_distance) in races.games() {
+            margin_of_error.push(find_winning_count(time, best_distance).unwrap_or(0));
         }
 
         margin_of_error.iter().product()
     }
 
-    pub fn run() {
-        let contents = fs::read_to_string("inputs/day6.txt").expect("File not found");
-
-        let result_brute_force = calculate_winning_margin_brute_force(&contents);
-        let result_quadratic = calculate_winning_margin_quadratic(&contents);
-
-        println!("Day 6 Part 1: {}", result_brute_force);
-        println!("Day 6 Part 1: {}", result_quadratic);
-    }
-
     #[cfg(test)]
     mod tests {
         use super::*;
@@ -138,7 +174,7 @@ pub mod part1 {
         fn test_calculate_winning_margin_brute_force() {
             assert_eq!(
                 calculate_winning_margin_brute_force(
-                    "Time:      7  15   30\nDistance:  9  40  200"
+                    &Day6::parse("Time:      7  15   30\nDistance:  9  40  200").unwrap()
                 ),
                 288
             );
@@ -147,11 +183,44 @@ pub mod part1 {
         #[test]
         fn test_calculate_winning_margin_quadratirc() {
             assert_eq!(
-                calculate_winning_margin_quadratic("Time:      7  15   30\nDistance:  9  40  200"),
+                calculate_winning_margin_quadratic(
+                    &Day6::parse("Time:      7  15   30\nDistance:  9  40  200").unwrap()
+                ),
                 288
             );
         }
 
+        // Past 2^53 an f64 can no longer tell a tie from a win, so compare against brute force on
+        // races whose records sit exactly at, just below and just above a reachable distance
+        #[test]
+        fn test_winning_margin_precision_boundaries() {
+            let time = 94_906_267;
+            let tie = 12_345_678 * (time - 12_345_678);
+            assert!(((time * time) as f64) > 2f64.powi(53));
+
+            for best_distance in [tie - 1, tie, tie + 1] {
+                let races = Races {
+                    times: vec![time],
+                    distances: vec![best_distance],
+                };
+
+                assert_eq!(
+                    calculate_winning_margin_quadratic(&races),
+                    calculate_winning_margin_brute_force(&races)
+                );
+            }
+        }
+
+        #[test]
+        fn test_winning_holds() {
+            assert_eq!(winning_holds(7, 9), Some((2, 5)));
+            assert_eq!(winning_holds(30, 200), Some((11, 19)));
+            assert_eq!(winning_holds(4, 4), None);
+            assert_eq!(winning_holds(4, 3), Some((2, 2)));
+            assert_eq!(winning_holds(3, 10), None);
+            assert_eq!(winning_holds(u64::MAX, u64::MAX), Some((2, u64::MAX - 2)));
+        }
+
         #[test]
         fn test_distance1() {
             assert_eq!(calculate_distance(7, 0), 0);
@@ -196,70 +265,27 @@ pub mod part1 {
 
 pub mod part2 {
     use super::*;
-    use regex::Regex;
-    use std::fs;
 
-    fn calculate_winnings_brute_force(contents: &str) -> u64 {
-        let lines: Vec<&str> = contents.lines().collect();
+    pub fn calculate_winnings_brute_force(races: &Races) -> BigUint {
+        let (time, best_distance) = races.single_race();
 
-        let lines_parsed: Vec<&str> = lines
-            .iter()
-            .map(|line| {
-                let (_, [numbers]) = Regex::new(r"^.*: (.*)$")
-                    .expect("Invalid regex")
-                    .captures(line)
-                    .expect("Failed to parse line")
-                    .extract();
-
-                numbers
-            })
-            .collect();
-
-        let time = lines_parsed[0].replace(' ', "").parse::<u64>().unwrap();
-        let best_distance = lines_parsed[1].replace(' ', "").parse::<u64>().unwrap();
-
-        let mut count_winnable = 0;
-        for t in 0..(time + 1) {
-            let hold = time - t;
-
-            if calculate_distance(time, hold) > best_distance {
-                count_winnable += 1;
+        let mut count_winnable = BigUint::zero();
+        let mut hold = BigUint::zero();
+        while hold <= time {
+            if &hold * (&time - &hold) > best_distance {
+                count_winnable += 1u32;
             }
+            hold += 1u32;
         }
 
         count_winnable
     }
 
-    fn calculate_winnings_quadratic(contents: &str) -> u64 {
-        let lines: Vec<&str> = contents.lines().collect();
+    pub fn calculate_winnings_quadratic(races: &Races) -> BigUint {
+        let (time, best_distance) = races.single_race();
 
-        let lines_parsed: Vec<&str> = lines
-            .iter()
-            .map(|line| {
-                let (_, [numbers]) = Regex::new(r"^.*: (.*)$")
-                    .expect("Invalid regex")
-                    .captures(line)
-                    .expect("Failed to parse line")
-                    .extract();
-
-                numbers
-            })
-            .collect();
-
-        let time = lines_parsed[0].replace(' ', "").parse::<u64>().unwrap();
-        let best_distance = lines_parsed[1].replace(' ', "").parse::<u64>().unwrap();
-
-        find_winning_count(time, best_distance).unwrap_or(0)
-    }
-
-    pub fn run() {
-        let contents = fs::read_to_string("inputs/day6.txt").expect("File not found");
-
-        let result_brute_force = calculate_winnings_brute_force(&contents);
-        let result_quadratic = calculate_winnings_quadratic(&contents);
-
-        println!("Day 6 Part 2: {}", result_brute_force);
-        println!("Day 6 Part 2: {}", result_quadratic);
+        winning_hold_range(&time, &best_distance)
+            .map_or_else(BigUint::zero, |(start, end)| end - start + BigUint::one())
     }
 
     #[cfg(test)]
@@ -269,17 +295,40 @@ pub mod part2 {
         #[test]
         fn test_calculate_winnings_brute_force() {
             assert_eq!(
-                calculate_winnings_brute_force("Time:      7  15   30\nDistance:  9  40  200"),
-                71503
+                calculate_winnings_brute_force(
+                    &Day6::parse("Time:      7  15   30\nDistance:  9  40  200").unwrap()
+                ),
+                BigUint::from(71503u32)
             );
         }
 
         #[test]
         fn test_calculate_winnings_quadratic() {
             assert_eq!(
-                calculate_winnings_quadratic("Time:      7  15   30\nDistance:  9  40  200"),
-                71503
+                calculate_winnings_quadratic(
+                    &Day6::parse("Time:      7  15   30\nDistance:  9  40  200").unwrap()
+                ),
+                BigUint::from(71503u32)
             );
         }
+
+        #[test]
+        fn test_joined_past_u64() {
+            // Each race alone fits in a u64 and is won by a single hold, joined they do not fit
+            let races = Day6::parse(
+                "Time: 6000000000 6000000000\nDistance: 8999999999999999999 8999999999999999999",
+            )
+            .unwrap();
+
+            assert_eq!(Day6::part1(&races), 1);
+            assert_eq!(Day6::part2(&races).to_string(), "56920997889355383297");
+        }
+
+        #[test]
+        fn test_mismatched_counts() {
+            let error = Day6::parse("Time: 7 15 30\nDistance: 9 40").unwrap_err();
+
+            assert_eq!((error.line, error.text.as_str()), (2, "Distance: 9 40"));
+        }
     }
 }
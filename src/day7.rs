@@ -1,123 +1,180 @@
-use std::{cmp::Ordering, collections::HashMap, u64};
+use crate::{
+    error::{number, parse_lines, split_once, ParseError},
+    solution::Solution,
+};
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, HashMap},
+    fmt::Display,
+};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, PartialOrd, Ord, Hash)]
+pub enum HandType {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAKind,
+    FullHouse,
+    FourOfAKind,
+    FiveOfAKind,
+}
 
-fn calculate_card_rank(value: &char, wildcard_value: u64) -> u64 {
-    match value {
-        '0' | '1' | '2' | '3' | '4' | '5' | '6' | '7' | '8' | '9' => {
-            value.to_string().parse::<u64>().unwrap()
+impl HandType {
+    // Classifies up to five cards from how often each label appears, in any order
+    pub fn from_counts(counts: &[usize]) -> Self {
+        let mut counts = counts.to_vec();
+        counts.sort_unstable_by(|a, b| b.cmp(a));
+
+        match counts.as_slice() {
+            [5, ..] => HandType::FiveOfAKind,
+            [4, ..] => HandType::FourOfAKind,
+            [3, 2, ..] => HandType::FullHouse,
+            [3, ..] => HandType::ThreeOfAKind,
+            [2, 2, ..] => HandType::TwoPair,
+            [2, ..] => HandType::OnePair,
+            _ => HandType::HighCard,
         }
-        'T' => 10,
-        'J' => wildcard_value,
-        'Q' => 12,
-        'K' => 13,
-        'A' => 14,
-        _ => 0,
     }
-}
 
-fn calculate_hand_rank_wildcard(cards: &Vec<Card>, wildcard: bool) -> u64 {
-    if !wildcard {
-        return calculate_hand_rank(cards);
+    // The best type reachable by adding one wild card, which always joins the largest group
+    pub fn promote(self) -> Self {
+        match self {
+            HandType::HighCard => HandType::OnePair,
+            HandType::OnePair => HandType::ThreeOfAKind,
+            HandType::TwoPair => HandType::FullHouse,
+            HandType::ThreeOfAKind | HandType::FullHouse => HandType::FourOfAKind,
+            HandType::FourOfAKind | HandType::FiveOfAKind => HandType::FiveOfAKind,
+        }
     }
 
-    let mut ranks: Vec<u64> = vec![];
-
-    for rank in 1..15 {
-        let new_cards: Vec<Card> = cards
-            .clone()
-            .iter()
-            .map(|c: &Card| {
-                if c.rank == 1 {
-                    Card { rank }
-                } else {
-                    c.clone()
-                }
-            })
-            .collect();
+    // Types the cards other than `wild`, then promotes once for every wild card
+    pub fn classify(labels: &[char], wild: Option<char>) -> Self {
+        let (hand_type, wildcards) = Self::without_wild(labels, wild);
 
-        ranks.push(calculate_hand_rank(&new_cards));
+        (0..wildcards).fold(hand_type, |hand_type, _| hand_type.promote())
     }
 
-    *ranks.iter().max().unwrap_or(&0)
-}
-
-fn calculate_hand_rank(cards: &Vec<Card>) -> u64 {
-    let mut cards_sorted = (*cards).clone();
-    cards_sorted.sort();
+    // The type of the cards other than `wild`, and how many wild cards were set aside
+    pub fn without_wild(labels: &[char], wild: Option<char>) -> (Self, usize) {
+        let mut counts: HashMap<char, usize> = HashMap::new();
+        let mut wildcards = 0;
 
-    let counts = cards.iter().fold(HashMap::new(), |mut acc, card| {
-        *acc.entry(card.rank).or_insert(0) += 1;
-        acc
-    });
+        for &label in labels {
+            if Some(label) == wild {
+                wildcards += 1;
+            } else {
+                *counts.entry(label).or_insert(0) += 1;
+            }
+        }
 
-    let mut real_counts: Vec<u64> = counts.values().cloned().collect();
-    real_counts.sort_by(|a, b| b.cmp(a));
+        let counts: Vec<usize> = counts.into_values().collect();
 
-    // Five of a kind, where all five cards have the same label: AAAAA
-    if real_counts[0] == 5 {
-        return 7;
+        (HandType::from_counts(&counts), wildcards)
     }
+}
 
-    // Four of a kind, where four cards have the same label and one card has a different label: AA8AA
-    if real_counts[0] == 4 {
-        return 6;
+impl Display for HandType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            HandType::HighCard => "High card",
+            HandType::OnePair => "One pair",
+            HandType::TwoPair => "Two pair",
+            HandType::ThreeOfAKind => "Three of a kind",
+            HandType::FullHouse => "Full house",
+            HandType::FourOfAKind => "Four of a kind",
+            HandType::FiveOfAKind => "Five of a kind",
+        };
+
+        f.pad(name)
     }
+}
+
+// How two hands of the same type are told apart
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum TieBreak {
+    // Card by card in the order they were dealt
+    Positional,
+    // Card by card from each hand's strongest card down, as in poker
+    Strongest,
+}
+
+impl TieBreak {
+    pub fn order(&self, cards: &[Card]) -> Vec<Card> {
+        let mut cards = cards.to_vec();
+        if *self == TieBreak::Strongest {
+            cards.sort_by(|a, b| b.cmp(a));
+        }
 
-    // Full house, where three cards have the same label, and the remaining two cards share a different label: 23332
-    if real_counts[0] == 3 && real_counts[1] == 2 {
-        return 5;
+        cards
     }
+}
+
+// The rules a set of hands is ranked under: card labels from weakest to strongest, the label
+// that stands in for any other when typing a hand, and how ties between types are broken
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RuleSet {
+    pub order: Vec<char>,
+    pub wild: Option<char>,
+    pub tie_break: TieBreak,
+}
 
-    // Three of a kind, where three cards have the same label, and the remaining two cards are each different from any other card in the hand: TTT98
-    if real_counts[0] == 3 {
-        return 4;
+impl RuleSet {
+    pub fn new(order: &str, wild: Option<char>, tie_break: TieBreak) -> Self {
+        Self {
+            order: order.chars().collect(),
+            wild,
+            tie_break,
+        }
     }
 
-    // Two pair, where two cards share one label, two other cards share a second label, and the remaining card has a third label: 23432
-    if real_counts[0] == 2 && real_counts[1] == 2 {
-        return 3;
+    pub fn standard() -> Self {
+        Self::new("23456789TJQKA", None, TieBreak::Positional)
     }
 
-    // One pair, where two cards share one label, and the other three cards have a different label from the pair and each other: A23A4
-    if real_counts[0] == 2 {
-        return 2;
+    // Jacks become jokers: wild when typing a hand but the weakest card in a tie-break
+    pub fn jokers() -> Self {
+        Self::new("J23456789TQKA", Some('J'), TieBreak::Positional)
     }
 
-    // High card, where all cards' labels are distinct: 23456
-    1
+    pub fn card_rank(&self, label: char) -> Option<u64> {
+        self.order
+            .iter()
+            .position(|&other| other == label)
+            .map(|position| position as u64)
+    }
 }
 
-fn compare_hands(a: &Hand, b: &Hand) -> Ordering {
-    match a.rank.cmp(&b.rank) {
-        Ordering::Greater => Ordering::Greater,
-        Ordering::Less => Ordering::Less,
-        Ordering::Equal => {
-            for (a, b) in a.cards.iter().zip(b.cards.iter()) {
-                match a.rank.cmp(&b.rank) {
-                    Ordering::Greater => return Ordering::Greater,
-                    Ordering::Less => return Ordering::Less,
-                    Ordering::Equal => {}
-                }
-            }
+impl Display for RuleSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Cards {}", self.order.iter().collect::<String>())?;
+        match self.wild {
+            Some(wild) => write!(f, ", {} is wild", wild)?,
+            None => write!(f, ", nothing wild")?,
+        }
 
-            Ordering::Less
+        match self.tie_break {
+            TieBreak::Positional => write!(f, ", ties broken in dealt order"),
+            TieBreak::Strongest => write!(f, ", ties broken strongest card first"),
         }
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
-struct Card {
-    rank: u64,
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Card {
+    pub label: char,
+    pub rank: u64,
 }
 
 impl Card {
-    fn parse(value: &str, wildcard: bool) -> Self {
-        let chars: Vec<char> = value.chars().collect();
-        let val = chars[0];
-        let wildcard_value = if wildcard { 1 } else { 11 };
+    pub fn parse(label: char, rules: &RuleSet) -> Result<Self, ParseError> {
+        rules
+            .card_rank(label)
+            .map(|rank| Self::new(label, rank))
+            .ok_or_else(|| ParseError::new(&label.to_string(), "expected a card label, found"))
+    }
 
-        Self {
-            rank: calculate_card_rank(&val, wildcard_value),
-        }
+    pub fn new(label: char, rank: u64) -> Self {
+        Self { label, rank }
     }
 }
 
@@ -133,32 +190,73 @@ impl PartialOrd for Card {
     }
 }
 
+// A hand as dealt in the input, before a rule set types and ranks it
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Deal {
+    pub labels: Vec<char>,
+    pub bid: u64,
+}
+
+impl Deal {
+    // Reads `<labels> <bid>`, checking that every label is a card under `rules`
+    pub fn parse(string: &str, rules: &RuleSet) -> Result<Self, ParseError> {
+        let (labels, bid) = split_once(string.trim(), " ")?;
+        let cards: Vec<Card> = labels
+            .chars()
+            .map(|label| Card::parse(label, rules))
+            .collect::<Result<_, _>>()?;
+
+        if cards.len() != 5 {
+            return Err(ParseError::new(labels, "expected five cards, found"));
+        }
+
+        Ok(Self {
+            labels: cards.iter().map(|card| card.label).collect(),
+            bid: number(bid)?,
+        })
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
-struct Hand {
-    cards: Vec<Card>,
-    rank: u64,
-    bid: u64,
+pub struct Hand {
+    pub cards: Vec<Card>,
+    pub hand_type: HandType,
+    // The cards in the order the rule set compares them when two hands share a type
+    pub tie_break: Vec<Card>,
+    pub bid: u64,
 }
 
 impl Hand {
-    fn parse(string: &str, wildcard: bool) -> Self {
-        let parts: Vec<&str> = string.split_whitespace().collect();
-        let cards: Vec<Card> = parts[0]
-            .split("")
-            .filter(|s| !s.is_empty())
-            .map(|c| Card::parse(c, wildcard))
-            .collect();
+    pub fn parse(string: &str, rules: &RuleSet) -> Result<Self, ParseError> {
+        Self::new(&Deal::parse(string, rules)?, rules)
+    }
 
-        let bid = parts[1].parse::<u64>().unwrap();
-        let rank = calculate_hand_rank_wildcard(&cards, wildcard);
+    // Types and orders `deal` under `rules`, which may not know every label the deal uses
+    pub fn new(deal: &Deal, rules: &RuleSet) -> Result<Self, ParseError> {
+        let cards: Vec<Card> = deal
+            .labels
+            .iter()
+            .map(|&label| Card::parse(label, rules))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            hand_type: HandType::classify(&deal.labels, rules.wild),
+            tie_break: rules.tie_break.order(&cards),
+            cards,
+            bid: deal.bid,
+        })
+    }
 
-        Self { cards, rank, bid }
+    pub fn labels(&self) -> String {
+        self.cards.iter().map(|card| card.label).collect()
     }
 }
 
 impl Ord for Hand {
     fn cmp(&self, other: &Self) -> Ordering {
-        compare_hands(self, other)
+        self.hand_type
+            .cmp(&other.hand_type)
+            .then_with(|| self.tie_break.cmp(&other.tie_break))
     }
 }
 
@@ -168,33 +266,119 @@ impl PartialOrd for Hand {
     }
 }
 
-pub mod part1 {
-    use super::*;
-    use std::fs;
+// Types every deal under `rules` and sorts the hands from weakest to strongest
+pub fn ranked_hands(deals: &[Deal], rules: &RuleSet) -> Result<Vec<Hand>, ParseError> {
+    let mut hands: Vec<Hand> = deals
+        .iter()
+        .map(|deal| Hand::new(deal, rules))
+        .collect::<Result<_, _>>()?;
+    hands.sort();
 
-    fn total_winnings(contents: &str) -> u64 {
-        let mut hands: Vec<Hand> = vec![];
+    Ok(hands)
+}
+
+pub fn total_winnings(hands: &[Hand]) -> u64 {
+    hands
+        .iter()
+        .enumerate()
+        .map(|(i, h)| (i + 1) as u64 * h.bid)
+        .sum()
+}
 
-        for line in contents.lines() {
-            let hand = Hand::parse(line, false);
-            hands.push(hand);
+// One line per hand from weakest to strongest, showing why it landed where it did: its type,
+// which type the wild cards promoted it from, and the cards its tie-break compares
+pub fn explain(deals: &[Deal], rules: &RuleSet) -> Result<String, ParseError> {
+    let hands = ranked_hands(deals, rules)?;
+    let mut explanation = format!("{}\n", rules);
+
+    for (index, hand) in hands.iter().enumerate() {
+        let labels: Vec<char> = hand.cards.iter().map(|card| card.label).collect();
+        let tie_break: String = hand.tie_break.iter().map(|card| card.label).collect();
+        let rank = index as u64 + 1;
+
+        explanation += &format!(
+            "{:>5}. {}  {:<15}  tie-break {}  bid {} x {} = {}",
+            rank,
+            hand.labels(),
+            hand.hand_type,
+            tie_break,
+            hand.bid,
+            rank,
+            hand.bid * rank
+        );
+
+        match HandType::without_wild(&labels, rules.wild) {
+            (base, wildcards) if wildcards > 0 => {
+                let wild: String = labels.iter().filter(|&&l| Some(l) == rules.wild).collect();
+                explanation += &format!("  promoted from {} by {}\n", base, wild);
+            }
+            _ => explanation += "\n",
         }
+    }
+
+    explanation += &format!("Total: {}\n", total_winnings(&hands));
+
+    Ok(explanation)
+}
+
+// How many hands of each type a rule set produces, and its total winnings
+pub fn summarize(deals: &[Deal], rules: &RuleSet) -> Result<String, ParseError> {
+    let hands = ranked_hands(deals, rules)?;
+    let mut counts: BTreeMap<HandType, usize> = BTreeMap::new();
+
+    for hand in &hands {
+        *counts.entry(hand.hand_type).or_insert(0) += 1;
+    }
+
+    let mut summary = format!("{}\n", rules);
+    for (hand_type, count) in counts {
+        summary += &format!("{:>15}: {}\n", hand_type, count);
+    }
+    summary += &format!("Total: {}\n", total_winnings(&hands));
 
-        hands.sort();
+    Ok(summary)
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input = Vec<Deal>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    // The standard and joker rule sets share their labels, so checking against one covers both
+    fn parse(contents: &str) -> Result<Self::Input, ParseError> {
+        parse_lines(contents, |line| Deal::parse(line, &RuleSet::standard()))
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1::total_winnings(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2::total_winnings(input)
+    }
 
-        hands
-            .into_iter()
-            .enumerate()
-            .map(|(i, h)| (i + 1) as u64 * h.bid)
-            .sum()
+    // Per-hand detail is left to `explain`, which the binary's `--explain` prints
+    fn report(input: &Self::Input) -> Option<String> {
+        let summarize = |rules| summarize(input, &rules).expect("Hands are checked by Day7::parse");
+
+        Some(format!(
+            "{}\n{}",
+            summarize(RuleSet::standard()),
+            summarize(RuleSet::jokers())
+        ))
     }
+}
 
-    pub fn run() {
-        let contents = fs::read_to_string("inputs/day7.txt").expect("File not found");
+pub mod part1 {
+    use super::*;
 
-        let result = total_winnings(&contents);
+    pub fn total_winnings(deals: &[Deal]) -> u64 {
+        let hands =
+            ranked_hands(deals, &RuleSet::standard()).expect("Hands are checked by Day7::parse");
 
-        println!("Day 7 Part 1: {}", result);
+        super::total_winnings(&hands)
     }
 
     #[cfg(test)]
@@ -204,86 +388,140 @@ pub mod part1 {
         #[test]
         fn test_total_winnings() {
             assert_eq!(
-                total_winnings("32T3K 765\nT55J5 684\nKK677 28\nKTJJT 220\nQQQJA 483"),
+                total_winnings(
+                    &Day7::parse("32T3K 765\nT55J5 684\nKK677 28\nKTJJT 220\nQQQJA 483").unwrap()
+                ),
                 6440
             );
         }
 
         #[test]
         fn test_total_winnings2() {
-            assert_eq!(total_winnings("73642 1\n27438 2"), 4);
+            assert_eq!(total_winnings(&Day7::parse("73642 1\n27438 2").unwrap()), 4);
+        }
+
+        #[test]
+        fn test_hand_parse_error() {
+            let error = Day7::parse("32T3K 765\nT5XJ5 684").unwrap_err();
+
+            assert_eq!(error.text, "X");
+            assert!(Day7::parse("32T3 765").is_err());
         }
 
         // Five of a kind, where all five cards have the same label: AAAAA
         #[test]
         fn test_hand_rank1() {
-            assert_eq!(Hand::parse("AAAAA 1", false).rank, 7);
+            assert_eq!(
+                Hand::parse("AAAAA 1", &RuleSet::standard())
+                    .unwrap()
+                    .hand_type,
+                HandType::FiveOfAKind
+            );
         }
 
         // Four of a kind, where four cards have the same label and one card has a different label: AA8AA
         #[test]
         fn test_hand_rank2() {
-            assert_eq!(Hand::parse("AA8AA 1", false).rank, 6);
+            assert_eq!(
+                Hand::parse("AA8AA 1", &RuleSet::standard())
+                    .unwrap()
+                    .hand_type,
+                HandType::FourOfAKind
+            );
         }
 
         // Full house, where three cards have the same label, and the remaining two cards share a different label: 23332
         #[test]
         fn test_hand_rank3() {
-            assert_eq!(Hand::parse("23332 1", false).rank, 5);
+            assert_eq!(
+                Hand::parse("23332 1", &RuleSet::standard())
+                    .unwrap()
+                    .hand_type,
+                HandType::FullHouse
+            );
         }
 
         // Three of a kind, where three cards have the same label, and the remaining two cards are each different from any other card in the hand: TTT98
         #[test]
         fn test_hand_rank4() {
-            assert_eq!(Hand::parse("TTT98 1", false).rank, 4);
+            assert_eq!(
+                Hand::parse("TTT98 1", &RuleSet::standard())
+                    .unwrap()
+                    .hand_type,
+                HandType::ThreeOfAKind
+            );
         }
 
         // Two pair, where two cards share one label, two other cards share a second label, and the remaining card has a third label: 23432
         #[test]
         fn test_hand_rank5() {
-            assert_eq!(Hand::parse("23432 1", false).rank, 3);
+            assert_eq!(
+                Hand::parse("23432 1", &RuleSet::standard())
+                    .unwrap()
+                    .hand_type,
+                HandType::TwoPair
+            );
         }
 
         // One pair, where two cards share one label, and the other three cards have a different label from the pair and each other: A23A4
         #[test]
         fn test_hand_rank6() {
-            assert_eq!(Hand::parse("A23A4 1", false).rank, 2);
+            assert_eq!(
+                Hand::parse("A23A4 1", &RuleSet::standard())
+                    .unwrap()
+                    .hand_type,
+                HandType::OnePair
+            );
         }
 
         // High card, where all cards' labels are distinct: 23456
         #[test]
         fn test_hand_rank7() {
-            assert_eq!(Hand::parse("23456 1", false).rank, 1);
+            assert_eq!(
+                Hand::parse("23456 1", &RuleSet::standard())
+                    .unwrap()
+                    .hand_type,
+                HandType::HighCard
+            );
         }
-    }
-}
-pub mod part2 {
-    use super::*;
-    use std::fs;
 
-    fn total_winnings(contents: &str) -> u64 {
-        let mut hands: Vec<Hand> = vec![];
+        #[test]
+        fn test_house_rules() {
+            let deals = Day7::parse("A2345 1\n23456 2").unwrap();
+            let house = RuleSet::new("A23456789TJQK", None, TieBreak::Strongest);
+
+            assert_eq!(total_winnings(&deals), 4);
+            assert_eq!(
+                super::super::total_winnings(&ranked_hands(&deals, &house).unwrap()),
+                5
+            );
+            assert!(Hand::parse("2345Z 1", &house).is_err());
 
-        for line in contents.lines() {
-            let hand = Hand::parse(line, true);
-            hands.push(hand);
+            let short = RuleSet::new("23456789TJQK", None, TieBreak::Positional);
+            assert_eq!(ranked_hands(&deals, &short).unwrap_err().text, "A");
         }
 
-        hands.sort();
+        #[test]
+        fn test_explain() {
+            let deals = Day7::parse("32T3K 765\nKTJJT 220").unwrap();
+            let explanation = explain(&deals, &RuleSet::standard()).unwrap();
 
-        hands
-            .into_iter()
-            .enumerate()
-            .map(|(i, h)| (i + 1) as u64 * h.bid)
-            .sum()
+            assert_eq!(
+                explanation,
+                "Cards 23456789TJQKA, nothing wild, ties broken in dealt order\n    1. 32T3K  One pair         tie-break 32T3K  bid 765 x 1 = 765\n    2. KTJJT  Two pair         tie-break KTJJT  bid 220 x 2 = 440\nTotal: 1205\n"
+            );
+        }
     }
+}
 
-    pub fn run() {
-        let contents = fs::read_to_string("inputs/day7.txt").expect("File not found");
+pub mod part2 {
+    use super::*;
 
-        let result = total_winnings(&contents);
+    pub fn total_winnings(deals: &[Deal]) -> u64 {
+        let hands =
+            ranked_hands(deals, &RuleSet::jokers()).expect("Hands are checked by Day7::parse");
 
-        println!("Day 7 Part 2: {}", result);
+        super::total_winnings(&hands)
     }
 
     #[cfg(test)]
@@ -293,76 +531,199 @@ pub mod part2 {
         #[test]
         fn test_total_winnings() {
             assert_eq!(
-                total_winnings("32T3K 765\nT55J5 684\nKK677 28\nKTJJT 220\nQQQJA 483"),
+                total_winnings(
+                    &Day7::parse("32T3K 765\nT55J5 684\nKK677 28\nKTJJT 220\nQQQJA 483").unwrap()
+                ),
                 5905
             );
         }
 
+        #[test]
+        fn test_explain_promotions() {
+            let deals = Day7::parse("32T3K 765\nKTJJT 220\nJJJJJ 1").unwrap();
+            let explanation = explain(&deals, &RuleSet::jokers()).unwrap();
+
+            assert_eq!(
+                explanation,
+                "Cards J23456789TQKA, J is wild, ties broken in dealt order\n    1. 32T3K  One pair         tie-break 32T3K  bid 765 x 1 = 765\n    2. KTJJT  Four of a kind   tie-break KTJJT  bid 220 x 2 = 440  promoted from One pair by JJ\n    3. JJJJJ  Five of a kind   tie-break JJJJJ  bid 1 x 3 = 3  promoted from High card by JJJJJ\nTotal: 1208\n"
+            );
+        }
+
+        #[test]
+        fn test_summarize() {
+            let deals =
+                Day7::parse("32T3K 765\nT55J5 684\nKK677 28\nKTJJT 220\nQQQJA 483").unwrap();
+
+            assert_eq!(
+                summarize(&deals, &RuleSet::jokers()).unwrap(),
+                "Cards J23456789TQKA, J is wild, ties broken in dealt order\n       One pair: 1\n       Two pair: 1\n Four of a kind: 3\nTotal: 5905\n"
+            );
+        }
+
         // Five of a kind, where all five cards have the same label: AAAAA
         #[test]
         fn test_hand_rank1() {
-            assert_eq!(Hand::parse("AAAAA 1", true).rank, 7);
+            assert_eq!(
+                Hand::parse("AAAAA 1", &RuleSet::jokers())
+                    .unwrap()
+                    .hand_type,
+                HandType::FiveOfAKind
+            );
         }
 
         // Four of a kind, where four cards have the same label and one card has a different label: AA8AA
         #[test]
         fn test_hand_rank2() {
-            assert_eq!(Hand::parse("AA8AA 1", true).rank, 6);
+            assert_eq!(
+                Hand::parse("AA8AA 1", &RuleSet::jokers())
+                    .unwrap()
+                    .hand_type,
+                HandType::FourOfAKind
+            );
         }
 
         // Full house, where three cards have the same label, and the remaining two cards share a different label: 23332
         #[test]
         fn test_hand_rank3() {
-            assert_eq!(Hand::parse("23332 1", true).rank, 5);
+            assert_eq!(
+                Hand::parse("23332 1", &RuleSet::jokers())
+                    .unwrap()
+                    .hand_type,
+                HandType::FullHouse
+            );
         }
 
         // Three of a kind, where three cards have the same label, and the remaining two cards are each different from any other card in the hand: TTT98
         #[test]
         fn test_hand_rank4() {
-            assert_eq!(Hand::parse("TTT98 1", true).rank, 4);
+            assert_eq!(
+                Hand::parse("TTT98 1", &RuleSet::jokers())
+                    .unwrap()
+                    .hand_type,
+                HandType::ThreeOfAKind
+            );
         }
 
         // Two pair, where two cards share one label, two other cards share a second label, and the remaining card has a third label: 23432
         #[test]
         fn test_hand_rank5() {
-            assert_eq!(Hand::parse("23432 1", true).rank, 3);
+            assert_eq!(
+                Hand::parse("23432 1", &RuleSet::jokers())
+                    .unwrap()
+                    .hand_type,
+                HandType::TwoPair
+            );
         }
 
         // One pair, where two cards share one label, and the other three cards have a different label from the pair and each other: A23A4
         #[test]
         fn test_hand_rank6() {
-            assert_eq!(Hand::parse("A23A4 1", true).rank, 2);
+            assert_eq!(
+                Hand::parse("A23A4 1", &RuleSet::jokers())
+                    .unwrap()
+                    .hand_type,
+                HandType::OnePair
+            );
         }
 
         // High card, where all cards' labels are distinct: 23456
         #[test]
         fn test_hand_rank7() {
-            assert_eq!(Hand::parse("23456 1", true).rank, 1);
+            assert_eq!(
+                Hand::parse("23456 1", &RuleSet::jokers())
+                    .unwrap()
+                    .hand_type,
+                HandType::HighCard
+            );
         }
 
         #[test]
         fn test_hand_rank8() {
-            assert_eq!(Hand::parse("32T3K 1", true).rank, 2);
+            assert_eq!(
+                Hand::parse("32T3K 1", &RuleSet::jokers())
+                    .unwrap()
+                    .hand_type,
+                HandType::OnePair
+            );
         }
 
         #[test]
         fn test_hand_rank9() {
-            assert_eq!(Hand::parse("KK677 1", true).rank, 3);
+            assert_eq!(
+                Hand::parse("KK677 1", &RuleSet::jokers())
+                    .unwrap()
+                    .hand_type,
+                HandType::TwoPair
+            );
         }
 
         #[test]
         fn test_hand_rank10() {
-            assert_eq!(Hand::parse("T55J5 1", true).rank, 6);
+            assert_eq!(
+                Hand::parse("T55J5 1", &RuleSet::jokers())
+                    .unwrap()
+                    .hand_type,
+                HandType::FourOfAKind
+            );
         }
 
         #[test]
         fn test_hand_rank11() {
-            assert_eq!(Hand::parse("KTJJT 1", true).rank, 6);
+            assert_eq!(
+                Hand::parse("KTJJT 1", &RuleSet::jokers())
+                    .unwrap()
+                    .hand_type,
+                HandType::FourOfAKind
+            );
         }
 
         #[test]
         fn test_hand_rank12() {
-            assert_eq!(Hand::parse("QQQJA 1", true).rank, 6);
+            assert_eq!(
+                Hand::parse("QQQJA 1", &RuleSet::jokers())
+                    .unwrap()
+                    .hand_type,
+                HandType::FourOfAKind
+            );
+        }
+
+        #[test]
+        fn test_promotion_matches_substitution() {
+            let labels = ['J', '2', '3', 'A', 'K'];
+            let rules = RuleSet::jokers();
+
+            for index in 0..labels.len().pow(5) {
+                let hand: Vec<char> = (0..5)
+                    .map(|position| labels[index / labels.len().pow(position) % labels.len()])
+                    .collect();
+
+                let best = rules
+                    .order
+                    .iter()
+                    .map(|&substitute| {
+                        let replaced: Vec<char> = hand
+                            .iter()
+                            .map(|&label| if label == 'J' { substitute } else { label })
+                            .collect();
+
+                        HandType::classify(&replaced, None)
+                    })
+                    .max();
+
+                assert_eq!(
+                    Some(HandType::classify(&hand, rules.wild)),
+                    best,
+                    "{:?}",
+                    hand
+                );
+            }
+        }
+
+        #[test]
+        fn test_jokers_break_ties_low() {
+            let deals = Day7::parse("JKKK2 1\nQQQQ2 2").unwrap();
+
+            assert_eq!(total_winnings(&deals), 5);
         }
     }
 }
@@ -1,102 +1,371 @@
-use std::collections::HashMap;
+use crate::{
+    error::{parse_lines, split_once, ParseError},
+    solution::{Optional, Solution},
+};
+use num::integer::Integer;
+use std::collections::{HashMap, HashSet};
 
-#[derive(Debug)]
-struct Node {
-    node: String,
-    left: String,
-    right: String,
+#[derive(Debug, Clone)]
+pub struct Node {
+    pub node: String,
+    pub left: String,
+    pub right: String,
 }
 
 impl Node {
-    fn parse(node: &str) -> Self {
-        let (part1, part2) = node.split_once(" = ").unwrap();
-        let (left_node, right_node) = part2.split_once(", ").unwrap();
+    pub fn parse(node: &str) -> Result<Self, ParseError> {
+        let (part1, part2) = split_once(node, " = ")?;
+        let (left_node, right_node) = part2
+            .strip_prefix('(')
+            .and_then(|pair| pair.strip_suffix(')'))
+            .and_then(|pair| pair.split_once(", "))
+            .ok_or_else(|| ParseError::new(part2, "expected `(<left>, <right>)`, found"))?;
 
-        Self {
+        Ok(Self {
             node: part1.to_string(),
-            left: left_node.to_string().replace('(', ""),
-            right: right_node.to_string().replace(')', ""),
-        }
+            left: left_node.to_string(),
+            right: right_node.to_string(),
+        })
     }
 }
 
+// A node name as a quoted Graphviz ID. Names are whatever precedes ` = `, so they may hold
+// quotes or backslashes that would otherwise end the ID early.
+fn dot_id(name: &str) -> String {
+    format!("\"{}\"", name.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
 #[derive(Debug)]
-struct Map {
-    directions: Vec<String>,
-    num_directions: usize,
-    nodes: Vec<Node>,
-    nodes_map: HashMap<String, Node>,
+pub struct Map {
+    pub directions: Vec<String>,
+    pub num_directions: usize,
+    pub nodes: Vec<Node>,
+    pub nodes_map: HashMap<String, Node>,
 }
 
 impl Map {
-    fn parse(contents: &str) -> Self {
-        let (directions_str, nodes_str) = contents.split_once("\n\n").unwrap();
+    pub fn parse(contents: &str) -> Result<Self, ParseError> {
+        let (directions_str, nodes_str) = split_once(contents, "\n\n")?;
+        if let Some(index) = directions_str.find(|c| c != 'L' && c != 'R') {
+            let column = directions_str[..index].chars().count() + 1;
+
+            return Err(ParseError::new(
+                &directions_str[index..].chars().take(1).collect::<String>(),
+                "expected `L` or `R`, found",
+            )
+            .at(1, column));
+        }
+
         let directions: Vec<String> = directions_str
             .split("")
             .filter(|s| !s.is_empty())
             .map(|s| s.to_string())
             .collect();
-        let nodes: Vec<Node> = nodes_str.lines().map(Node::parse).collect();
+        if directions.is_empty() {
+            return Err(ParseError::new(directions_str, "expected directions, found").at(1, 1));
+        }
+
+        // The nodes start after the directions and the blank line
+        let offset = directions_str.matches('\n').count() + 2;
+        let nodes: Vec<Node> =
+            parse_lines(nodes_str, Node::parse).map_err(|error| error.below(offset))?;
         let num_directions = directions.len();
 
         let mut nodes_map: HashMap<String, Node> = HashMap::new();
-        for line in nodes_str.lines() {
-            let node = Node::parse(line);
-            nodes_map.insert(node.node.clone(), node);
+        for node in &nodes {
+            nodes_map.insert(node.node.clone(), node.clone());
         }
 
-        Self {
+        // Every node a path can step to has to be defined, otherwise walking the map would fail
+        for (index, node) in nodes.iter().enumerate() {
+            for target in [&node.left, &node.right] {
+                if !nodes_map.contains_key(target) {
+                    let line = nodes_str.lines().nth(index).unwrap_or_default();
+
+                    return Err(ParseError::new(target, "unknown node")
+                        .on_line(index, line)
+                        .below(offset));
+                }
+            }
+        }
+
+        Ok(Self {
             directions,
             num_directions,
             nodes,
             nodes_map,
-        }
+        })
     }
 
-    fn get_direction(&self, dir_key: usize) -> String {
+    pub fn get_direction(&self, dir_key: usize) -> String {
         self.directions[dir_key % self.num_directions].clone()
     }
 
-    fn find_node(&self, key: &String) -> &Node {
+    pub fn find_node(&self, key: &String) -> &Node {
         self.nodes_map.get(key).unwrap()
     }
 
-    fn count_steps(&self, initial_node: &Node) -> u64 {
+    pub fn step(&self, node: &Node, dir_key: usize) -> &Node {
+        if self.get_direction(dir_key) == "L" {
+            self.find_node(&node.left)
+        } else {
+            self.find_node(&node.right)
+        }
+    }
+
+    // Walks from `initial_node` until a (node, instruction index) state repeats. Every walk
+    // does, as there are only finitely many states.
+    pub fn find_cycle(&self, initial_node: &Node) -> Cycle {
+        let mut seen: HashMap<(&str, usize), u64> = HashMap::new();
+        let mut hits: Vec<u64> = vec![];
+        let mut node = initial_node;
+        let mut steps: u64 = 0;
+
+        let tail = loop {
+            let state = (node.node.as_str(), steps as usize % self.num_directions);
+            if let Some(&first) = seen.get(&state) {
+                break first;
+            }
+
+            seen.insert(state, steps);
+            if node.node.ends_with('Z') {
+                hits.push(steps);
+            }
+
+            node = self.step(node, steps as usize);
+            steps += 1;
+        };
+
+        let offsets = hits.split_off(hits.partition_point(|&hit| hit < tail));
+
+        Cycle {
+            start: initial_node.node.clone(),
+            tail,
+            length: steps - tail,
+            tail_hits: hits,
+            offsets,
+        }
+    }
+
+    // The nodes visited in `steps` steps from `initial_node`, including where it starts
+    pub fn walk<'a>(&'a self, initial_node: &'a Node, steps: u64) -> Vec<&'a Node> {
+        let mut path = vec![initial_node];
+
+        for dir_key in 0..steps as usize {
+            path.push(self.step(path[dir_key], dir_key));
+        }
+
+        path
+    }
+
+    // The network as a Graphviz digraph. Start nodes are green and end nodes red; edges taken
+    // along `path` (as returned by `walk`) are drawn in bold blue.
+    pub fn to_dot(&self, path: &[&Node]) -> String {
+        let taken: HashSet<(&str, String)> = path
+            .windows(2)
+            .enumerate()
+            .map(|(dir_key, pair)| (pair[0].node.as_str(), self.get_direction(dir_key)))
+            .collect();
+
+        let mut dot = String::from("digraph day8 {\n");
+        for node in &self.nodes {
+            if node.node.ends_with('A') {
+                dot += &format!(
+                    "  {} [style=filled, fillcolor=palegreen];\n",
+                    dot_id(&node.node)
+                );
+            } else if node.node.ends_with('Z') {
+                dot += &format!(
+                    "  {} [style=filled, fillcolor=salmon];\n",
+                    dot_id(&node.node)
+                );
+            }
+        }
+
+        for node in &self.nodes {
+            for (direction, target) in [("L", &node.left), ("R", &node.right)] {
+                let style = if taken.contains(&(node.node.as_str(), direction.to_string())) {
+                    ", color=blue, penwidth=2"
+                } else {
+                    ""
+                };
+
+                dot += &format!(
+                    "  {} -> {} [label=\"{}\"{}];\n",
+                    dot_id(&node.node),
+                    dot_id(target),
+                    direction,
+                    style
+                );
+            }
+        }
+
+        dot + "}\n"
+    }
+
+    pub fn cycles(&self) -> Vec<Cycle> {
+        self.nodes
+            .iter()
+            .filter(|node| node.node.ends_with('A'))
+            .map(|node| self.find_cycle(node))
+            .collect()
+    }
+
+    // Steps until the first Z node, or None once the walk repeats a state without reaching one
+    pub fn count_steps(&self, initial_node: &Node) -> Option<u64> {
+        let mut seen: HashSet<(&str, usize)> = HashSet::new();
         let mut next_node = initial_node;
         let mut count: u64 = 0;
         let mut dir_key: usize = 0;
 
         while !next_node.node.ends_with('Z') {
-            let direction = self.get_direction(dir_key);
-            next_node = if direction == "L" {
-                self.find_node(&next_node.left)
-            } else {
-                self.find_node(&next_node.right)
-            };
+            if !seen.insert((next_node.node.as_str(), dir_key % self.num_directions)) {
+                return None;
+            }
 
+            next_node = self.step(next_node, dir_key);
             dir_key += 1;
             count += 1;
         }
 
-        count
+        Some(count)
     }
 }
-pub mod part1 {
-    use super::*;
-    use std::fs;
 
-    fn count_steps(contents: &str) -> u64 {
-        let map = Map::parse(contents);
-        let initial_node = map.find_node(&"AAA".to_string());
+// The shape of one walk: `tail` steps lead into a loop of `length` steps that repeats forever.
+// A Z node is reached at each step in `tail_hits`, and at each step in `offsets` plus any
+// multiple of `length`. Offsets lie in `tail..tail + length`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Cycle {
+    pub start: String,
+    pub tail: u64,
+    pub length: u64,
+    pub tail_hits: Vec<u64>,
+    pub offsets: Vec<u64>,
+}
 
-        map.count_steps(initial_node)
+impl Cycle {
+    pub fn is_hit(&self, steps: u64) -> bool {
+        if steps < self.tail {
+            self.tail_hits.contains(&steps)
+        } else {
+            let phase = (steps - self.tail) % self.length;
+            self.offsets.contains(&(self.tail + phase))
+        }
+    }
+}
+
+// Solves x = a (mod m) and x = b (mod n) for moduli that need not be coprime, giving the
+// combined residue modulo lcm(m, n)
+pub fn crt(a: i128, m: i128, b: i128, n: i128) -> Option<(i128, i128)> {
+    let gcd = m.extended_gcd(&n);
+    if (b - a) % gcd.gcd != 0 {
+        return None;
+    }
+
+    let lcm = m / gcd.gcd * n;
+    let k = ((b - a) / gcd.gcd * gcd.x).rem_euclid(n / gcd.gcd);
+
+    Some(((a + m * k).rem_euclid(lcm), lcm))
+}
+
+// The first step at which every walk stands on a Z node, or None if that never happens or there
+// are no walks at all
+pub fn first_common_hit(cycles: &[Cycle]) -> Option<u64> {
+    if cycles.is_empty() {
+        return None;
+    }
+
+    let settled = cycles.iter().map(|cycle| cycle.tail).max().unwrap_or(0);
+
+    // Before every walk has entered its loop, a common hit is one of the longest tail's hits
+    let early = cycles
+        .iter()
+        .max_by_key(|cycle| cycle.tail)
+        .into_iter()
+        .flat_map(|cycle| cycle.tail_hits.iter().copied())
+        .find(|&steps| cycles.iter().all(|cycle| cycle.is_hit(steps)));
+    if early.is_some() {
+        return early;
+    }
+
+    // Afterwards every walk is periodic, so combine each choice of offsets with the CRT
+    let mut residues: Vec<(i128, i128)> = vec![(0, 1)];
+    for cycle in cycles {
+        let length = cycle.length as i128;
+        let mut combined: Vec<(i128, i128)> = residues
+            .iter()
+            .flat_map(|&(a, m)| {
+                cycle
+                    .offsets
+                    .iter()
+                    .filter_map(move |&offset| crt(a, m, offset as i128 % length, length))
+            })
+            .collect();
+
+        combined.sort_unstable();
+        combined.dedup();
+        residues = combined;
     }
 
-    pub fn run() {
-        let contents = fs::read_to_string("inputs/day8.txt").expect("File not found");
-        let result = count_steps(&contents);
+    residues
+        .into_iter()
+        .map(|(residue, modulus)| {
+            let settled = settled as i128;
+            let laps = Integer::div_ceil(&(settled - residue).max(0), &modulus);
+
+            (residue + laps * modulus) as u64
+        })
+        .min()
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Input = Map;
+    type Answer1 = Optional<u64>;
+    type Answer2 = Optional<u64>;
 
-        println!("Day 8 Part 1: {}", result);
+    fn parse(contents: &str) -> Result<Self::Input, ParseError> {
+        Map::parse(contents)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        Optional(part1::count_steps(input))
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        Optional(part2::count_steps(input))
+    }
+
+    fn report(input: &Self::Input) -> Option<String> {
+        let cycles = input.cycles();
+        let mut report = String::new();
+
+        for cycle in &cycles {
+            report += &format!(
+                "{}: tail {}, cycle {}, Z at {:?} then {:?} + {}k\n",
+                cycle.start, cycle.tail, cycle.length, cycle.tail_hits, cycle.offsets, cycle.length
+            );
+        }
+
+        match first_common_hit(&cycles) {
+            Some(steps) => report += &format!("All on Z after {} steps\n", steps),
+            None => report += "The walks are never all on Z at once\n",
+        }
+
+        Some(report)
+    }
+}
+
+pub mod part1 {
+    use super::*;
+
+    // None when the network has no `AAA` node, as in the part 2 example, or no way to `ZZZ`
+    pub fn count_steps(map: &Map) -> Option<u64> {
+        let initial_node = map.nodes_map.get("AAA")?;
+
+        map.count_steps(initial_node)
     }
 
     #[cfg(test)]
@@ -105,45 +374,84 @@ pub mod part1 {
 
         #[test]
         fn test_day8_part1a() {
+            assert_eq!(
+                count_steps(&Day8::parse("RL\n\nAAA = (BBB, CCC)\nBBB = (DDD, EEE)\nCCC = (ZZZ, GGG)\nDDD = (DDD, DDD)\nEEE = (EEE, EEE)\nGGG = (GGG, GGG)\nZZZ = (ZZZ, ZZZ)").unwrap()),
+                Some(2)
+            );
+        }
+
+        #[test]
+        fn test_day8_part1b() {
             assert_eq!(
                 count_steps(
-                    "RL\n\nAAA = (BBB, CCC)\nBBB = (DDD, EEE)\nCCC = (ZZZ, GGG)\nDDD = (DDD, DDD)\nEEE = (EEE, EEE)\nGGG = (GGG, GGG)\nZZZ = (ZZZ, ZZZ)"
+                    &Day8::parse("LLR\n\nAAA = (BBB, BBB)\nBBB = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)")
+                        .unwrap()
                 ),
-                2
+                Some(6)
             );
         }
 
         #[test]
-        fn test_day8_part1b() {
+        fn test_day8_part1_without_answer() {
+            let ghosts = Day8::parse(
+                "LR\n\n11A = (11B, XXX)\n11B = (XXX, 11Z)\n11Z = (11B, XXX)\nXXX = (XXX, XXX)",
+            )
+            .unwrap();
+            assert_eq!(count_steps(&ghosts), None);
+            assert_eq!(Day8::part1(&ghosts).to_string(), "none");
+
+            let stuck =
+                Day8::parse("L\n\nAAA = (BBB, ZZZ)\nBBB = (AAA, AAA)\nZZZ = (ZZZ, ZZZ)").unwrap();
+            assert_eq!(count_steps(&stuck), None);
+        }
+
+        #[test]
+        fn test_day8_parse_errors() {
+            let error = Day8::parse("LR\n\nAAA = (BBB, ZZZ)\nZZZ = (ZZZ, ZZZ)").unwrap_err();
             assert_eq!(
-                count_steps("LLR\n\nAAA = (BBB, BBB)\nBBB = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)"),
-                6
+                (error.message.as_str(), error.text.as_str()),
+                ("unknown node", "BBB")
             );
+
+            let error = Day8::parse("LXR\n\nAAA = (AAA, AAA)").unwrap_err();
+            assert_eq!(error.text, "X");
         }
-    }
-}
 
-pub mod part2 {
-    use super::*;
-    use num::integer::lcm;
-    use std::fs;
+        #[test]
+        fn test_day8_to_dot() {
+            let map =
+                Day8::parse("LLR\n\nAAA = (BBB, BBB)\nBBB = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)").unwrap();
+            let path = map.walk(map.find_node(&"AAA".to_string()), 3);
 
-    fn count_steps(contents: &str) -> u64 {
-        let map = Map::parse(contents);
+            assert_eq!(
+                path.iter()
+                    .map(|node| node.node.as_str())
+                    .collect::<Vec<_>>(),
+                vec!["AAA", "BBB", "AAA", "BBB"]
+            );
+            assert_eq!(
+                map.to_dot(&path),
+                "digraph day8 {\n  \"AAA\" [style=filled, fillcolor=palegreen];\n  \"ZZZ\" [style=filled, fillcolor=salmon];\n  \"AAA\" -> \"BBB\" [label=\"L\", color=blue, penwidth=2];\n  \"AAA\" -> \"BBB\" [label=\"R\", color=blue, penwidth=2];\n  \"BBB\" -> \"AAA\" [label=\"L\", color=blue, penwidth=2];\n  \"BBB\" -> \"ZZZ\" [label=\"R\"];\n  \"ZZZ\" -> \"ZZZ\" [label=\"L\"];\n  \"ZZZ\" -> \"ZZZ\" [label=\"R\"];\n}\n"
+            );
+        }
 
-        map.nodes
-            .iter()
-            .filter(|node| node.node.ends_with('A'))
-            .map(|initial_node| map.count_steps(initial_node))
-            .fold(1, lcm)
-    }
+        #[test]
+        fn test_day8_to_dot_escapes_names() {
+            let map = Day8::parse("L\n\na\"b = (c\\d, a\"b)\nc\\d = (c\\d, c\\d)").unwrap();
 
-    pub fn run() {
-        let contents = fs::read_to_string("inputs/day8.txt").expect("File not found");
+            assert_eq!(
+                map.to_dot(&[]),
+                "digraph day8 {\n  \"a\\\"b\" -> \"c\\\\d\" [label=\"L\"];\n  \"a\\\"b\" -> \"a\\\"b\" [label=\"R\"];\n  \"c\\\\d\" -> \"c\\\\d\" [label=\"L\"];\n  \"c\\\\d\" -> \"c\\\\d\" [label=\"R\"];\n}\n"
+            );
+        }
+    }
+}
 
-        let result = count_steps(&contents);
+pub mod part2 {
+    use super::*;
 
-        println!("Day 8 Part 2: {}", result);
+    pub fn count_steps(map: &Map) -> Option<u64> {
+        first_common_hit(&map.cycles())
     }
 
     #[cfg(test)]
@@ -153,11 +461,68 @@ pub mod part2 {
         #[test]
         fn test_day8_part2a() {
             assert_eq!(
-                count_steps(
-                    "LR\n\n11A = (11B, XXX)\n11B = (XXX, 11Z)\n11Z = (11B, XXX)\n22A = (22B, XXX)\n22B = (22C, 22C)\n22C = (22Z, 22Z)\n22Z = (22B, 22B)\nXXX = (XXX, XXX)"
-                ),
-                6
+                count_steps(&Day8::parse("LR\n\n11A = (11B, XXX)\n11B = (XXX, 11Z)\n11Z = (11B, XXX)\n22A = (22B, XXX)\n22B = (22C, 22C)\n22C = (22Z, 22Z)\n22Z = (22B, 22B)\nXXX = (XXX, XXX)").unwrap()),
+                Some(6)
             );
         }
+
+        #[test]
+        fn test_day8_part2_without_answer() {
+            let never = Day8::parse("L\n\n11A = (11B, 11B)\n11B = (11Z, 11Z)\n11Z = (11B, 11B)\n22A = (22B, 22B)\n22B = (22C, 22C)\n22C = (22Z, 22Z)\n22Z = (22C, 22C)").unwrap();
+            let no_ghosts = Day8::parse("L\n\nBBB = (ZZZ, ZZZ)\nZZZ = (ZZZ, ZZZ)").unwrap();
+
+            assert_eq!(Day8::part2(&never).to_string(), "none");
+            assert_eq!(count_steps(&no_ghosts), None);
+        }
+
+        #[test]
+        fn test_day8_find_cycle() {
+            let map = Day8::parse("LR\n\n11A = (11B, XXX)\n11B = (XXX, 11Z)\n11Z = (11B, XXX)\n22A = (22B, XXX)\n22B = (22C, 22C)\n22C = (22Z, 22Z)\n22Z = (22B, 22B)\nXXX = (XXX, XXX)").unwrap();
+            let cycles = map.cycles();
+
+            assert_eq!(
+                (cycles[0].tail, cycles[0].length, cycles[0].offsets.clone()),
+                (1, 2, vec![2])
+            );
+            assert_eq!(
+                (cycles[1].tail, cycles[1].length, cycles[1].offsets.clone()),
+                (1, 6, vec![3, 6])
+            );
+        }
+
+        #[test]
+        fn test_day8_offset_cycles() {
+            // Z is first reached after 3 and 4 steps, then every 2 and 3 steps
+            let map = Day8::parse("L\n\n11A = (11B, 11B)\n11B = (11C, 11C)\n11C = (11Z, 11Z)\n11Z = (11D, 11D)\n11D = (11Z, 11Z)\n22A = (22B, 22B)\n22B = (22C, 22C)\n22C = (22D, 22D)\n22D = (22Z, 22Z)\n22Z = (22E, 22E)\n22E = (22F, 22F)\n22F = (22Z, 22Z)").unwrap();
+
+            assert_eq!(count_steps(&map), Some(7));
+        }
+
+        #[test]
+        fn test_day8_hit_in_tail() {
+            let map = Day8::parse("L\n\n11A = (11Z, 11Z)\n11Z = (11B, 11B)\n11B = (11B, 11B)\n22A = (22Z, 22Z)\n22Z = (22Z, 22Z)").unwrap();
+            let cycles = map.cycles();
+
+            assert_eq!(
+                (cycles[0].tail_hits.clone(), cycles[0].offsets.clone()),
+                (vec![1], vec![])
+            );
+            assert_eq!(first_common_hit(&cycles), Some(1));
+        }
+
+        #[test]
+        fn test_day8_never_aligned() {
+            // One ghost is on Z after an even number of steps, the other after an odd number
+            let map = Day8::parse("L\n\n11A = (11B, 11B)\n11B = (11Z, 11Z)\n11Z = (11B, 11B)\n22A = (22B, 22B)\n22B = (22C, 22C)\n22C = (22Z, 22Z)\n22Z = (22C, 22C)").unwrap();
+
+            assert_eq!(first_common_hit(&map.cycles()), None);
+        }
+
+        #[test]
+        fn test_day8_crt() {
+            assert_eq!(crt(2, 4, 0, 6), Some((6, 12)));
+            assert_eq!(crt(1, 4, 0, 6), None);
+            assert_eq!(crt(0, 1, 3, 5), Some((3, 5)));
+        }
     }
 }
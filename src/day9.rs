@@ -1,56 +1,153 @@
-fn extrapolate_digits(digits: &Vec<i64>, direction: &ExtrapolateDirection) -> i64 {
-    if digits.iter().all(|&digit| digit == 0) {
-        return 0;
+use crate::{
+    error::{number, parse_lines, ParseError},
+    solution::Solution,
+};
+use num::{BigInt, BigRational, One, Zero};
+
+// The polynomial through a sequence of equally spaced values, in Newton's forward difference
+// form: p(x) = sum over k of Δ^k y(0) * C(x, k), where x = 0 is the first value
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct NewtonSeries {
+    differences: Vec<BigRational>,
+    len: usize,
+}
+
+impl NewtonSeries {
+    // Takes differences until a row is all zeros. A sequence whose last row of differences (a
+    // single value) is still non-zero does not pin down a polynomial.
+    pub fn fit(values: &[i64]) -> Result<Self, String> {
+        if values.is_empty() {
+            return Err("expected at least one value".to_string());
+        }
+
+        let mut row: Vec<BigRational> = values
+            .iter()
+            .map(|&value| BigRational::from_integer(BigInt::from(value)))
+            .collect();
+        let mut differences = vec![];
+
+        while !row.iter().all(Zero::is_zero) {
+            if row.len() == 1 {
+                return Err("differences never reach all zeros".to_string());
+            }
+
+            differences.push(row[0].clone());
+            row = row.windows(2).map(|pair| &pair[1] - &pair[0]).collect();
+        }
+
+        Ok(Self {
+            differences,
+            len: values.len(),
+        })
     }
 
-    let mut next = vec![];
+    // The zero polynomial and constants both count as degree 0
+    pub fn degree(&self) -> usize {
+        self.differences.len().saturating_sub(1)
+    }
+
+    // p(x) for any x, including before the first value (negative x) or after the last
+    pub fn value_at(&self, x: i64) -> BigRational {
+        let x = BigRational::from_integer(BigInt::from(x));
+        let mut binomial = BigRational::one();
+        let mut value = BigRational::zero();
 
-    for i in 0..digits.len() - 1 {
-        if *direction == ExtrapolateDirection::Right {
-            next.push(digits[i + 1] - digits[i]);
-        } else {
-            next.push(digits[i] - digits[i + 1]);
+        for (k, difference) in self.differences.iter().enumerate() {
+            if k > 0 {
+                let k = BigRational::from_integer(BigInt::from(k));
+                binomial = binomial * (&x - &k + BigRational::one()) / k;
+            }
+
+            value += difference * &binomial;
         }
+
+        value
     }
 
-    let key = if *direction == ExtrapolateDirection::Right {
-        digits.len() - 1
-    } else {
-        0
-    };
+    // The value `steps` places beyond the last value, or before the first one
+    pub fn extrapolate(&self, direction: &ExtrapolateDirection, steps: u64) -> BigRational {
+        match direction {
+            ExtrapolateDirection::Right => self.value_at(self.len as i64 - 1 + steps as i64),
+            ExtrapolateDirection::Left => self.value_at(-(steps as i64)),
+        }
+    }
+}
 
-    digits[key] + extrapolate_digits(&next, direction)
+// Integer values and integer steps keep every term of the series whole, so the result is an
+// exact integer, though not necessarily one that fits in an i64
+pub fn extrapolate_digits(digits: &[i64], direction: &ExtrapolateDirection) -> BigInt {
+    NewtonSeries::fit(digits)
+        .expect("Sequences are checked by Day9::parse")
+        .extrapolate(direction, 1)
+        .to_integer()
 }
 
-fn parse_line(line: &str) -> Vec<i64> {
-    line.split_whitespace()
-        .map(|s| s.parse::<i64>().unwrap())
-        .collect()
+pub fn parse_line(line: &str) -> Result<Vec<i64>, ParseError> {
+    let digits: Vec<i64> = line
+        .split_whitespace()
+        .map(number)
+        .collect::<Result<_, _>>()?;
+    NewtonSeries::fit(&digits).map_err(|message| ParseError::new(line, message + " in"))?;
+
+    Ok(digits)
 }
 
-fn extrapolate(contents: &str, direction: &ExtrapolateDirection) -> i64 {
-    contents
-        .lines()
-        .map(parse_line)
-        .map(|digits| extrapolate_digits(&digits, direction))
+pub fn extrapolate(sequences: &[Vec<i64>], direction: &ExtrapolateDirection) -> BigInt {
+    sequences
+        .iter()
+        .map(|digits| extrapolate_digits(digits, direction))
         .sum()
 }
 
 #[derive(Debug, Eq, PartialEq, PartialOrd, Ord)]
-enum ExtrapolateDirection {
+pub enum ExtrapolateDirection {
     Left,
     Right,
 }
-pub mod part1 {
-    use super::*;
-    use std::fs;
 
-    pub fn run() {
-        let contents = fs::read_to_string("inputs/day9.txt").expect("File not found");
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Input = Vec<Vec<i64>>;
+    type Answer1 = BigInt;
+    type Answer2 = BigInt;
+
+    fn parse(contents: &str) -> Result<Self::Input, ParseError> {
+        parse_lines(contents, parse_line)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1::next_value_sum(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2::previous_value_sum(input)
+    }
+
+    fn report(input: &Self::Input) -> Option<String> {
+        let mut report = String::new();
+
+        for (index, digits) in input.iter().enumerate() {
+            let series = NewtonSeries::fit(digits).expect("Sequences are checked by Day9::parse");
+
+            report += &format!(
+                "Line {}: degree {}, previous {}, next {}\n",
+                index + 1,
+                series.degree(),
+                series.extrapolate(&ExtrapolateDirection::Left, 1),
+                series.extrapolate(&ExtrapolateDirection::Right, 1)
+            );
+        }
+
+        Some(report)
+    }
+}
 
-        let result = extrapolate(&contents, &ExtrapolateDirection::Right);
+pub mod part1 {
+    use super::*;
 
-        println!("Day 9 Part 1: {}", result);
+    pub fn next_value_sum(sequences: &[Vec<i64>]) -> BigInt {
+        extrapolate(sequences, &ExtrapolateDirection::Right)
     }
 
     #[cfg(test)]
@@ -60,34 +157,107 @@ pub mod part1 {
         #[test]
         fn test_day9_part1a() {
             assert_eq!(
-                extrapolate("0 3 6 9 12 15", &ExtrapolateDirection::Right),
-                18
+                extrapolate(
+                    &Day9::parse("0 3 6 9 12 15").unwrap(),
+                    &ExtrapolateDirection::Right
+                ),
+                BigInt::from(18)
             );
         }
 
         #[test]
         fn test_day9_part1b() {
             assert_eq!(
-                extrapolate("1 3 6 10 15 21", &ExtrapolateDirection::Right),
-                28
+                extrapolate(
+                    &Day9::parse("1 3 6 10 15 21").unwrap(),
+                    &ExtrapolateDirection::Right
+                ),
+                BigInt::from(28)
             );
         }
 
         #[test]
         fn test_day9_part1c() {
             assert_eq!(
-                extrapolate("10 13 16 21 30 45", &ExtrapolateDirection::Right),
-                68
+                extrapolate(
+                    &Day9::parse("10 13 16 21 30 45").unwrap(),
+                    &ExtrapolateDirection::Right
+                ),
+                BigInt::from(68)
             );
         }
 
         #[test]
         fn test_day9_part1d() {
             assert_eq!(
-                extrapolate("0 3 6 9 12 15", &ExtrapolateDirection::Right)
-                    + extrapolate("1 3 6 10 15 21", &ExtrapolateDirection::Right)
-                    + extrapolate("10 13 16 21 30 45", &ExtrapolateDirection::Right),
-                114
+                extrapolate(
+                    &Day9::parse("0 3 6 9 12 15").unwrap(),
+                    &ExtrapolateDirection::Right
+                ) + extrapolate(
+                    &Day9::parse("1 3 6 10 15 21").unwrap(),
+                    &ExtrapolateDirection::Right
+                ) + extrapolate(
+                    &Day9::parse("10 13 16 21 30 45").unwrap(),
+                    &ExtrapolateDirection::Right
+                ),
+                BigInt::from(114)
+            );
+        }
+
+        #[test]
+        fn test_day9_degree() {
+            let fit = |digits: &[i64]| NewtonSeries::fit(digits).unwrap().degree();
+
+            assert_eq!(fit(&[0, 3, 6, 9, 12, 15]), 1);
+            assert_eq!(fit(&[1, 3, 6, 10, 15, 21]), 2);
+            assert_eq!(fit(&[10, 13, 16, 21, 30, 45]), 3);
+            assert_eq!(fit(&[7, 7, 7]), 0);
+            assert_eq!(fit(&[0, 0]), 0);
+        }
+
+        #[test]
+        fn test_day9_extrapolate_steps() {
+            let series = NewtonSeries::fit(&[1, 3, 6, 10, 15, 21]).unwrap();
+            let triangle = |n: i64| BigRational::from_integer(BigInt::from((n + 1) * (n + 2) / 2));
+
+            assert_eq!(
+                series.extrapolate(&ExtrapolateDirection::Right, 0),
+                triangle(5)
+            );
+            assert_eq!(
+                series.extrapolate(&ExtrapolateDirection::Right, 10),
+                triangle(15)
+            );
+            assert_eq!(
+                series.extrapolate(&ExtrapolateDirection::Left, 3),
+                triangle(-3)
+            );
+            assert_eq!(series.value_at(-1), BigRational::zero());
+        }
+
+        #[test]
+        fn test_day9_past_i64() {
+            let sequences = Day9::parse(&format!("{} 0 {}", -i64::MAX, i64::MAX)).unwrap();
+
+            assert_eq!(
+                next_value_sum(&sequences).to_string(),
+                "18446744073709551614"
+            );
+            assert_eq!(Day9::part2(&sequences).to_string(), "-18446744073709551614");
+        }
+
+        #[test]
+        fn test_day9_never_settles() {
+            assert_eq!(
+                NewtonSeries::fit(&[1, 2, 4, 8, 16]),
+                Err("differences never reach all zeros".to_string())
+            );
+            assert!(NewtonSeries::fit(&[]).is_err());
+
+            let error = Day9::parse("0 3 6 9\n1 2 4 8 16").unwrap_err();
+            assert_eq!(
+                (error.text.as_str(), error.message.as_str()),
+                ("1 2 4 8 16", "differences never reach all zeros in")
             );
         }
     }
@@ -95,14 +265,9 @@ pub mod part1 {
 
 pub mod part2 {
     use super::*;
-    use std::fs;
-
-    pub fn run() {
-        let contents = fs::read_to_string("inputs/day9.txt").expect("File not found");
-
-        let result = extrapolate(&contents, &ExtrapolateDirection::Left);
 
-        println!("Day 9 Part 2: {}", result);
+    pub fn previous_value_sum(sequences: &[Vec<i64>]) -> BigInt {
+        extrapolate(sequences, &ExtrapolateDirection::Left)
     }
 
     #[cfg(test)]
@@ -112,18 +277,28 @@ pub mod part2 {
         #[test]
         fn test_day9_part2a() {
             assert_eq!(
-                extrapolate("10 13 16 21 30 45", &ExtrapolateDirection::Left),
-                5
+                extrapolate(
+                    &Day9::parse("10 13 16 21 30 45").unwrap(),
+                    &ExtrapolateDirection::Left
+                ),
+                BigInt::from(5)
             );
         }
 
         #[test]
         fn test_day9_part2b() {
             assert_eq!(
-                extrapolate("0 3 6 9 12 15", &ExtrapolateDirection::Left)
-                    + extrapolate("1 3 6 10 15 21", &ExtrapolateDirection::Left)
-                    + extrapolate("10 13 16 21 30 45", &ExtrapolateDirection::Left),
-                2
+                extrapolate(
+                    &Day9::parse("0 3 6 9 12 15").unwrap(),
+                    &ExtrapolateDirection::Left
+                ) + extrapolate(
+                    &Day9::parse("1 3 6 10 15 21").unwrap(),
+                    &ExtrapolateDirection::Left
+                ) + extrapolate(
+                    &Day9::parse("10 13 16 21 30 45").unwrap(),
+                    &ExtrapolateDirection::Left
+                ),
+                BigInt::from(2)
             );
         }
     }
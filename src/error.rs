@@ -0,0 +1,210 @@
+use std::{fmt::Display, io, str::FromStr};
+
+// A malformed piece of puzzle input. Parsers only know the offending text; whoever splits the
+// input into lines places the error with `on_line` or `at`, and `for_day` adds the day once the
+// error reaches the registry. A line of 0 means the error could not be placed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: u32,
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(text: &str, message: impl Into<String>) -> Self {
+        Self {
+            day: 0,
+            line: 0,
+            column: 0,
+            text: text.to_string(),
+            message: message.into(),
+        }
+    }
+
+    // Places the error at a 1-based line and column, unless a parser further down already did
+    pub fn at(mut self, line: usize, column: usize) -> Self {
+        if self.line == 0 {
+            self.line = line;
+            self.column = column;
+        }
+
+        self
+    }
+
+    // Places an error raised while parsing `line`, the input's line at 0-based `index`. The error
+    // only carries a copy of the offending text, so the column is where that text first appears
+    // in the line, or the line's start if it does not. When the same text also appears earlier,
+    // e.g. inside a valid token, the column points there; parsers that know the real position
+    // should use `at` instead.
+    pub fn on_line(self, index: usize, line: &str) -> Self {
+        let column = line
+            .find(self.text.as_str())
+            .map_or(1, |offset| line[..offset].chars().count() + 1);
+
+        self.at(index + 1, column)
+    }
+
+    // Moves an error placed within a section of the input down by the `lines` before the section
+    pub fn below(mut self, lines: usize) -> Self {
+        if self.line > 0 {
+            self.line += lines;
+        }
+
+        self
+    }
+
+    pub fn for_day(mut self, day: u32) -> Self {
+        self.day = day;
+        self
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Day {}", self.day)?;
+        if self.line > 0 {
+            write!(f, ", line {}, column {}", self.line, self.column)?;
+        }
+
+        write!(f, ": {} `{}`", self.message, self.text)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+// A file or inline value from the command line, such as a vocabulary or a bag, that could not be
+// read or did not parse. `path` is None for an inline value.
+#[derive(Debug)]
+pub enum LoadError {
+    Read {
+        path: String,
+        error: io::Error,
+    },
+    Parse {
+        path: Option<String>,
+        error: ParseError,
+    },
+}
+
+impl Display for LoadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LoadError::Read { path, error } => write!(f, "Could not read {}: {}", path, error),
+            LoadError::Parse { path, error } => {
+                if let Some(path) = path {
+                    write!(f, "{}, ", path)?;
+                }
+                if error.line > 0 {
+                    write!(f, "line {}, column {}: ", error.line, error.column)?;
+                }
+
+                write!(f, "{} `{}`", error.message, error.text)
+            }
+        }
+    }
+}
+
+impl std::error::Error for LoadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            LoadError::Read { error, .. } => Some(error),
+            LoadError::Parse { error, .. } => Some(error),
+        }
+    }
+}
+
+// Parses a number with surrounding whitespace ignored, reporting the trimmed text on failure
+pub fn number<T: FromStr>(text: &str) -> Result<T, ParseError> {
+    text.trim()
+        .parse()
+        .map_err(|_| ParseError::new(text.trim(), "expected a number, found"))
+}
+
+// Parses every line of `text` with `parse`, placing an error on the line it came from
+pub fn parse_lines<T, F>(text: &str, mut parse: F) -> Result<Vec<T>, ParseError>
+where
+    F: FnMut(&str) -> Result<T, ParseError>,
+{
+    text.lines()
+        .enumerate()
+        .map(|(index, line)| parse(line).map_err(|error| error.on_line(index, line)))
+        .collect()
+}
+
+// The blank-line separated sections of `text`, each with the 0-based index of its first line
+pub fn sections(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    text.split("\n\n").scan(0, |start, section| {
+        let first = *start;
+        *start += section.matches('\n').count() + 2;
+
+        Some((first, section))
+    })
+}
+
+// Splits `text` around the first `delimiter`, or reports that it is missing
+pub fn split_once<'a>(text: &'a str, delimiter: &str) -> Result<(&'a str, &'a str), ParseError> {
+    text.split_once(delimiter)
+        .ok_or_else(|| ParseError::new(text, format!("expected `{}` in", delimiter)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_lines() {
+        let error = parse_lines("1 2 3\n4 x 6", |line| {
+            line.split_whitespace()
+                .map(number::<u32>)
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .unwrap_err()
+        .for_day(9);
+
+        assert_eq!((error.day, error.line, error.column), (9, 2, 3));
+        assert_eq!(
+            error.to_string(),
+            "Day 9, line 2, column 3: expected a number, found `x`"
+        );
+    }
+
+    #[test]
+    fn test_placement() {
+        let error = ParseError::new("ABC", "unexpected").on_line(0, "xyz abc");
+        assert_eq!((error.line, error.column), (1, 1));
+
+        let error = ParseError::new("é", "unexpected").on_line(2, "aé").below(4);
+        assert_eq!((error.line, error.column), (7, 2));
+
+        // The innermost placement wins
+        let error = ParseError::new("x", "unexpected").at(3, 5).on_line(0, "x");
+        assert_eq!((error.line, error.column), (3, 5));
+    }
+
+    #[test]
+    fn test_unplaced() {
+        let error = ParseError::new("abc", "unexpected").for_day(1);
+
+        assert_eq!((error.line, error.column), (0, 0));
+        assert_eq!(error.to_string(), "Day 1: unexpected `abc`");
+    }
+
+    #[test]
+    fn test_sections() {
+        assert_eq!(
+            sections("a\nb\n\nc\n\nd\ne").collect::<Vec<_>>(),
+            vec![(0, "a\nb"), (3, "c"), (5, "d\ne")]
+        );
+    }
+
+    #[test]
+    fn test_split_once() {
+        assert_eq!(split_once("a: b", ":"), Ok(("a", " b")));
+        assert_eq!(
+            split_once("a b", ":").unwrap_err().message,
+            "expected `:` in"
+        );
+    }
+}
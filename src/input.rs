@@ -0,0 +1,110 @@
+use std::{
+    env,
+    fmt::Display,
+    fs,
+    io::{self, Read},
+    path::PathBuf,
+};
+
+const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+const DEFAULT_INPUT_DIR: &str = "inputs";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputSource {
+    // `inputs/dayN.txt`, or `$AOC_INPUT_DIR/dayN.txt` when the variable is set
+    Default,
+    Path(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    pub fn parse(value: &str) -> Self {
+        match value {
+            "-" => InputSource::Stdin,
+            path => InputSource::Path(PathBuf::from(path)),
+        }
+    }
+
+    fn describe(&self, day: u32) -> String {
+        match self {
+            InputSource::Default => default_path(day).display().to_string(),
+            InputSource::Path(path) => path.display().to_string(),
+            InputSource::Stdin => "stdin".to_string(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct InputError {
+    day: u32,
+    source: String,
+    error: io::Error,
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Could not read input for day {} from {}: {}",
+            self.day, self.source, self.error
+        )
+    }
+}
+
+impl std::error::Error for InputError {}
+
+fn default_path(day: u32) -> PathBuf {
+    let dir = env::var_os(INPUT_DIR_VAR).unwrap_or_else(|| DEFAULT_INPUT_DIR.into());
+
+    PathBuf::from(dir).join(format!("day{}.txt", day))
+}
+
+pub fn load(day: u32, source: &InputSource) -> Result<String, InputError> {
+    let contents = match source {
+        InputSource::Default => fs::read_to_string(default_path(day)),
+        InputSource::Path(path) => fs::read_to_string(path),
+        InputSource::Stdin => {
+            let mut contents = String::new();
+            io::stdin().read_to_string(&mut contents).map(|_| contents)
+        }
+    };
+
+    contents.map_err(|error| InputError {
+        day,
+        source: source.describe(day),
+        error,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_source() {
+        assert_eq!(InputSource::parse("-"), InputSource::Stdin);
+        assert_eq!(
+            InputSource::parse("example.txt"),
+            InputSource::Path(PathBuf::from("example.txt"))
+        );
+    }
+
+    #[test]
+    fn test_load_path() {
+        let path = PathBuf::from("inputs/day16-example.txt");
+
+        assert!(load(16, &InputSource::Path(path))
+            .unwrap()
+            .starts_with(".|...\\...."));
+    }
+
+    #[test]
+    fn test_missing_file_names_path() {
+        let path = PathBuf::from("inputs/does-not-exist.txt");
+        let error = load(3, &InputSource::Path(path)).unwrap_err();
+
+        assert!(error
+            .to_string()
+            .starts_with("Could not read input for day 3 from inputs/does-not-exist.txt: "));
+    }
+}
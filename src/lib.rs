@@ -0,0 +1,67 @@
+pub mod answers;
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day2;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod error;
+pub mod geometry;
+pub mod grid;
+pub mod input;
+pub mod runner;
+pub mod solution;
+
+use solution::Registry;
+
+// Every solved day, keyed by its day number
+pub fn registry() -> Registry {
+    let mut registry = Registry::new();
+
+    registry.register::<day1::Day1>(1);
+    registry.register::<day2::Day2>(2);
+    registry.register::<day3::Day3>(3);
+    registry.register::<day4::Day4>(4);
+    registry.register::<day5::Day5>(5);
+    registry.register::<day6::Day6>(6);
+    registry.register::<day7::Day7>(7);
+    registry.register::<day8::Day8>(8);
+    registry.register::<day9::Day9>(9);
+    registry.register::<day10::Day10>(10);
+    registry.register::<day11::Day11>(11);
+    registry.register::<day12::Day12>(12);
+    registry.register::<day13::Day13>(13);
+    registry.register::<day14::Day14>(14);
+    registry.register::<day15::Day15>(15);
+    registry.register::<day16::Day16>(16);
+    registry.register::<day17::Day17>(17);
+    registry.register::<day18::Day18>(18);
+    registry.register::<day19::Day19>(19);
+    registry.register::<day20::Day20>(20);
+    registry.register::<day21::Day21>(21);
+    registry.register::<day22::Day22>(22);
+    registry.register::<day23::Day23>(23);
+    registry.register::<day24::Day24>(24);
+    registry.register::<day25::Day25>(25);
+
+    registry
+}
@@ -1,98 +1,383 @@
-mod day1;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-mod day14;
-mod day15;
-mod day16;
-mod day17;
-mod day18;
-mod day19;
-mod day2;
-mod day20;
-mod day21;
-mod day22;
-mod day23;
-mod day24;
-mod day25;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod day9;
-
-use std::env;
+use advent_of_code_2023::{
+    answers::{Answers, Verdict},
+    day2::Bag,
+    day7::{self, Day7, RuleSet},
+    day8::Map,
+    input::{self, InputSource},
+    registry,
+    runner::{run_day, DayReport, Table},
+    solution::{Part, Registry, Solution},
+};
+use std::{env, fs};
+
+const ANSWERS_FILE: &str = "answers.toml";
+
+struct Args {
+    verify: bool,
+    dot: bool,
+    path: Option<String>,
+    record: bool,
+    report: bool,
+    explain: bool,
+    bag: Option<String>,
+    days: String,
+    part: Option<String>,
+    input: InputSource,
+}
 
 fn main() {
-    let (day, part) = parse_args();
-
-    match (day.as_str(), part.as_str()) {
-        ("1", "1") => day1::part1::run(),
-        ("1", "2") => day1::part2::run(),
-        ("2", "1") => day2::part1::run(),
-        ("2", "2") => day2::part2::run(),
-        ("3", "1") => day3::part1::run(),
-        ("3", "2") => day3::part2::run(),
-        ("4", "1") => day4::part1::run(),
-        ("4", "2") => day4::part2::run(),
-        ("5", "1") => day5::part1::run(),
-        ("5", "2") => day5::part2::run(),
-        ("6", "1") => day6::part1::run(),
-        ("6", "2") => day6::part2::run(),
-        ("7", "1") => day7::part1::run(),
-        ("7", "2") => day7::part2::run(),
-        ("8", "1") => day8::part1::run(),
-        ("8", "2") => day8::part2::run(),
-        ("9", "1") => day9::part1::run(),
-        ("9", "2") => day9::part2::run(),
-        ("10", "1") => day10::part1::run(),
-        ("10", "2") => day10::part2::run(),
-        ("11", "1") => day11::part1::run(),
-        ("11", "2") => day11::part2::run(),
-        ("12", "1") => day12::part1::run(),
-        ("12", "2") => day12::part2::run(),
-        ("13", "1") => day13::part1::run(),
-        ("13", "2") => day13::part2::run(),
-        ("14", "1") => day14::part1::run(),
-        ("14", "2") => day14::part2::run(),
-        ("15", "1") => day15::part1::run(),
-        ("15", "2") => day15::part2::run(),
-        ("16", "1") => day16::part1::run(),
-        ("16", "2") => day16::part2::run(),
-        ("17", "1") => day17::part1::run(),
-        ("17", "2") => day17::part2::run(),
-        ("18", "1") => day18::part1::run(),
-        ("18", "2") => day18::part2::run(),
-        ("19", "1") => day19::part1::run(),
-        ("19", "2") => day19::part2::run(),
-        ("20", "1") => day20::part1::run(),
-        ("20", "2") => day20::part2::run(),
-        ("21", "1") => day21::part1::run(),
-        ("21", "2") => day21::part2::run(),
-        ("22", "1") => day22::part1::run(),
-        ("22", "2") => day22::part2::run(),
-        ("23", "1") => day23::part1::run(),
-        ("23", "2") => day23::part2::run(),
-        ("24", "1") => day24::part1::run(),
-        ("24", "2") => day24::part2::run(),
-        ("25", "1") => day25::part1::run(),
-        ("25", "2") => day25::part2::run(),
-        _ => println!("Invalid day or part"),
+    let args = parse_args();
+
+    // Check the bag before solving anything so a bad one is reported up front
+    let mut registry = registry();
+    if let Some(bag) = &args.bag {
+        let bag = Bag::load(bag).unwrap_or_else(|error| {
+            eprintln!("Invalid --bag: {}", error);
+            std::process::exit(1);
+        });
+
+        registry.configure(2, Box::new(bag));
+    }
+
+    if args.dot {
+        dot(&args.input, args.path.as_deref());
+        return;
+    }
+
+    let selected: Option<Vec<u32>> = match args.days.as_str() {
+        "all" => Some(registry.days().collect()),
+        days => parse_days(days),
+    };
+
+    let selected = match selected {
+        Some(selected) if selected.iter().all(|&day| registry.get(day).is_some()) => selected,
+        _ => {
+            println!("Invalid day or part");
+            std::process::exit(1);
+        }
+    };
+
+    let parts = match args.part.as_deref().map(Part::parse) {
+        None => vec![Part::One, Part::Two],
+        Some(Some(part)) => vec![part],
+        Some(None) => {
+            println!("Invalid day or part");
+            std::process::exit(1);
+        }
+    };
+
+    if args.input != InputSource::Default && selected.len() != 1 {
+        eprintln!("--input can only be used with a single day");
+        std::process::exit(1);
+    }
+
+    if args.report {
+        let [day] = selected.as_slice() else {
+            eprintln!("--report can only be used with a single day");
+            std::process::exit(1);
+        };
+
+        report(&registry, *day, &args.input);
+        return;
+    }
+
+    if args.explain {
+        if selected != [7] {
+            eprintln!("--explain can only be used with day 7");
+            std::process::exit(1);
+        }
+
+        explain(&parts, &args.input);
+        return;
+    }
+
+    if args.verify {
+        let passed = verify(&registry, &selected, &parts, &args.input, args.record);
+        std::process::exit(if passed { 0 } else { 1 });
+    }
+
+    // A single day and part keeps the plain one-line answer
+    if let ([day], [part]) = (selected.as_slice(), parts.as_slice()) {
+        let contents = input::load(*day, &args.input).unwrap_or_else(|error| {
+            eprintln!("{}", error);
+            std::process::exit(1);
+        });
+
+        let solver = registry.get(*day).unwrap();
+        match solver.run(&contents, *part) {
+            Ok(answer) => println!("Day {} Part {}: {}", day, part, answer),
+            Err(error) => {
+                eprintln!("{}", error);
+                std::process::exit(1);
+            }
+        }
+
+        return;
+    }
+
+    let mut failed = false;
+    let reports: Vec<DayReport> = selected
+        .iter()
+        .filter_map(|&day| {
+            let contents = match input::load(day, &args.input) {
+                Ok(contents) => contents,
+                Err(error) => {
+                    eprintln!("Skipping day {}: {}", day, error);
+                    return None;
+                }
+            };
+
+            match run_day(day, registry.get(day).unwrap(), &contents, &parts) {
+                Ok(report) => Some(report),
+                Err(error) => {
+                    eprintln!("{}", error);
+                    failed = true;
+                    None
+                }
+            }
+        })
+        .collect();
+
+    if !reports.is_empty() {
+        print!("{}", Table::new(&reports));
+    }
+
+    if failed || reports.is_empty() {
+        std::process::exit(1);
     }
 }
 
-fn parse_args() -> (String, String) {
-    let args: Vec<String> = env::args().collect();
-    if args.len() < 3 {
-        println!("Usage: cargo run <day> <part>");
+// Prints the day's breakdown of how its answers come about
+fn report(registry: &Registry, day: u32, source: &InputSource) {
+    let contents = input::load(day, source).unwrap_or_else(|error| {
+        eprintln!("{}", error);
         std::process::exit(1);
+    });
+
+    match registry.get(day).unwrap().run_report(&contents) {
+        Ok(Some(report)) => print!("{}", report),
+        Ok(None) => {
+            eprintln!("Day {} has no report", day);
+            std::process::exit(1);
+        }
+        Err(error) => {
+            eprintln!("{}", error);
+            std::process::exit(1);
+        }
     }
+}
+
+// Prints how day 7 ranked every hand, under the part 1 rules, the part 2 (joker) rules or both
+fn explain(parts: &[Part], source: &InputSource) {
+    let contents = input::load(7, source).unwrap_or_else(|error| {
+        eprintln!("{}", error);
+        std::process::exit(1);
+    });
+
+    let deals = Day7::parse(&contents).unwrap_or_else(|error| {
+        eprintln!("{}", error.for_day(7));
+        std::process::exit(1);
+    });
 
-    let day = &args[1];
-    let part = &args[2];
+    let explanations: Vec<String> = parts
+        .iter()
+        .map(|part| {
+            let rules = match part {
+                Part::One => RuleSet::standard(),
+                Part::Two => RuleSet::jokers(),
+            };
 
-    (day.to_string(), part.to_string())
+            day7::explain(&deals, &rules).expect("Hands are checked by Day7::parse")
+        })
+        .collect();
+
+    print!("{}", explanations.join("\n"));
+}
+
+// Prints day 8's network in Graphviz format, highlighting the walk from `start` until it loops
+fn dot(source: &InputSource, start: Option<&str>) {
+    let contents = input::load(8, source).unwrap_or_else(|error| {
+        eprintln!("{}", error);
+        std::process::exit(1);
+    });
+
+    let map = Map::parse(&contents).unwrap_or_else(|error| {
+        eprintln!("{}", error.for_day(8));
+        std::process::exit(1);
+    });
+
+    let path = match start {
+        None => vec![],
+        Some(start) => match map.nodes_map.get(start) {
+            Some(node) => {
+                let cycle = map.find_cycle(node);
+                map.walk(node, cycle.tail + cycle.length)
+            }
+            None => {
+                eprintln!("Unknown node `{}`", start);
+                std::process::exit(1);
+            }
+        },
+    };
+
+    print!("{}", map.to_dot(&path));
+}
+
+// Checks every selected answer against `answers.toml`, or overwrites the recorded answers with
+// the current ones when `record` is set. Returns false if any answer differs.
+fn verify(
+    registry: &Registry,
+    days: &[u32],
+    parts: &[Part],
+    source: &InputSource,
+    record: bool,
+) -> bool {
+    let mut answers = match fs::read_to_string(ANSWERS_FILE) {
+        Ok(contents) => Answers::parse(&contents).unwrap_or_else(|error| {
+            eprintln!("Invalid {}: {}", ANSWERS_FILE, error);
+            std::process::exit(1);
+        }),
+        Err(_) => Answers::default(),
+    };
+
+    let mut passed = true;
+    for &day in days {
+        let contents = match input::load(day, source) {
+            Ok(contents) => contents,
+            Err(error) => {
+                eprintln!("Skipping day {}: {}", day, error);
+                continue;
+            }
+        };
+
+        let report = match run_day(day, registry.get(day).unwrap(), &contents, parts) {
+            Ok(report) => report,
+            Err(error) => {
+                eprintln!("{}", error);
+                passed = false;
+                continue;
+            }
+        };
+
+        for (part, result) in &report.parts {
+            if record {
+                answers.record(day, *part, &result.answer);
+                println!("Day {} Part {}: RECORDED {}", day, part, result.answer);
+                continue;
+            }
+
+            let verdict = answers.verify(day, *part, &result.answer);
+            passed &= !matches!(verdict, Verdict::Fail { .. });
+
+            println!("Day {} Part {}: {}", day, part, verdict);
+        }
+    }
+
+    if record {
+        fs::write(ANSWERS_FILE, answers.to_string()).unwrap_or_else(|error| {
+            eprintln!("Could not write {}: {}", ANSWERS_FILE, error);
+            std::process::exit(1);
+        });
+    }
+
+    passed
+}
+
+// Accepts a single day (`5`) or an inclusive range (`1-10`)
+fn parse_days(days: &str) -> Option<Vec<u32>> {
+    match days.split_once('-') {
+        Some((start, end)) => {
+            let (start, end) = (start.parse::<u32>().ok()?, end.parse::<u32>().ok()?);
+
+            (start <= end).then(|| (start..=end).collect())
+        }
+        None => Some(vec![days.parse::<u32>().ok()?]),
+    }
+}
+
+fn usage() -> ! {
+    println!(
+        "Usage: cargo run [verify [--record]] <day|first-last|all> [part] [--input <path|->] [--report] [--explain] [--bag <limits|path>]"
+    );
+    println!("       cargo run dot [--input <path|->] [--path <start>]");
+    std::process::exit(1);
+}
+
+fn parse_args() -> Args {
+    let mut positional: Vec<String> = vec![];
+    let mut input = InputSource::Default;
+    let mut record = false;
+    let mut report = false;
+    let mut explain = false;
+    let mut bag = None;
+    let mut path = None;
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--input" => match args.next() {
+                Some(path) => input = InputSource::parse(&path),
+                None => usage(),
+            },
+            "--bag" => match args.next() {
+                Some(value) => bag = Some(value),
+                None => usage(),
+            },
+            "--path" => match args.next() {
+                Some(start) => path = Some(start),
+                None => usage(),
+            },
+            "--record" => record = true,
+            "--report" => report = true,
+            "--explain" => explain = true,
+            _ => positional.push(arg),
+        }
+    }
+
+    // `dot` only ever draws day 8, so it takes no day or part
+    if positional.first().is_some_and(|command| command == "dot") {
+        if positional.len() > 1 || record || report || explain || bag.is_some() {
+            usage();
+        }
+
+        return Args {
+            verify: false,
+            dot: true,
+            path,
+            record,
+            report,
+            explain,
+            bag,
+            days: "8".to_string(),
+            part: None,
+            input,
+        };
+    }
+
+    let verify = positional
+        .first()
+        .is_some_and(|command| command == "verify");
+    if (record && !verify) || ((report || explain) && verify) || path.is_some() {
+        usage();
+    }
+
+    let mut positional = positional.into_iter().skip(verify as usize);
+    let days = match (positional.next(), verify) {
+        (Some(days), _) => days,
+        (None, true) => "all".to_string(),
+        (None, false) => usage(),
+    };
+
+    Args {
+        verify,
+        dot: false,
+        path,
+        record,
+        report,
+        explain,
+        bag,
+        days,
+        part: positional.next(),
+        input,
+    }
 }
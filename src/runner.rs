@@ -0,0 +1,198 @@
+use crate::{
+    error::ParseError,
+    solution::{Part, Solver},
+};
+use std::{
+    fmt::Display,
+    time::{Duration, Instant},
+};
+
+pub struct PartReport {
+    pub answer: String,
+    pub elapsed: Duration,
+}
+
+pub struct DayReport {
+    pub day: u32,
+    pub parse: Duration,
+    pub parts: Vec<(Part, PartReport)>,
+}
+
+impl DayReport {
+    pub fn total(&self) -> Duration {
+        self.parse
+            + self
+                .parts
+                .iter()
+                .map(|(_, part)| part.elapsed)
+                .sum::<Duration>()
+    }
+
+    fn part(&self, part: Part) -> Option<&PartReport> {
+        self.parts
+            .iter()
+            .find(|(p, _)| *p == part)
+            .map(|(_, report)| report)
+    }
+}
+
+// Parses the input once and solves each requested part from it, timing every step separately
+pub fn run_day(
+    day: u32,
+    solver: &dyn Solver,
+    contents: &str,
+    parts: &[Part],
+) -> Result<DayReport, ParseError> {
+    let start = Instant::now();
+    let input = solver.parse(contents)?;
+    let parse = start.elapsed();
+
+    let parts = parts
+        .iter()
+        .map(|&part| {
+            let start = Instant::now();
+            let answer = solver.solve(part, input.as_ref());
+
+            (
+                part,
+                PartReport {
+                    answer,
+                    elapsed: start.elapsed(),
+                },
+            )
+        })
+        .collect();
+
+    Ok(DayReport { day, parse, parts })
+}
+
+pub struct Table {
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    const HEADER: [&'static str; 7] = [
+        "Day", "Part 1", "Part 2", "Parse", "Solve 1", "Solve 2", "Total",
+    ];
+
+    pub fn new(reports: &[DayReport]) -> Self {
+        let time = |duration: Option<Duration>| match duration {
+            Some(duration) => format!("{:.2?}", duration),
+            None => "-".to_string(),
+        };
+
+        let mut rows: Vec<Vec<String>> = reports
+            .iter()
+            .map(|report| {
+                let one = report.part(Part::One);
+                let two = report.part(Part::Two);
+
+                vec![
+                    report.day.to_string(),
+                    one.map_or("-".to_string(), |p| p.answer.clone()),
+                    two.map_or("-".to_string(), |p| p.answer.clone()),
+                    time(Some(report.parse)),
+                    time(one.map(|p| p.elapsed)),
+                    time(two.map(|p| p.elapsed)),
+                    time(Some(report.total())),
+                ]
+            })
+            .collect();
+
+        let sum = |f: &dyn Fn(&DayReport) -> Duration| reports.iter().map(f).sum::<Duration>();
+        let solve = |part: Part| {
+            move |report: &DayReport| report.part(part).map_or(Duration::ZERO, |p| p.elapsed)
+        };
+
+        rows.push(vec![
+            "Total".to_string(),
+            String::new(),
+            String::new(),
+            time(Some(sum(&|report| report.parse))),
+            time(Some(sum(&solve(Part::One)))),
+            time(Some(sum(&solve(Part::Two)))),
+            time(Some(sum(&|report| report.total()))),
+        ]);
+
+        Self { rows }
+    }
+}
+
+impl Display for Table {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let widths: Vec<usize> = (0..Table::HEADER.len())
+            .map(|column| {
+                self.rows
+                    .iter()
+                    .map(|row| row[column].len())
+                    .chain(std::iter::once(Table::HEADER[column].len()))
+                    .max()
+                    .unwrap()
+            })
+            .collect();
+
+        let line = |f: &mut std::fmt::Formatter<'_>, cells: &[&str]| {
+            let cells: Vec<String> = cells
+                .iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{:>width$}", cell, width = width))
+                .collect();
+
+            writeln!(f, "| {} |", cells.join(" | "))
+        };
+
+        line(f, &Table::HEADER)?;
+
+        let separator: Vec<String> = widths.iter().map(|width| "-".repeat(*width)).collect();
+        writeln!(f, "|-{}-|", separator.join("-|-"))?;
+
+        for row in &self.rows {
+            let cells: Vec<&str> = row.iter().map(|cell| cell.as_str()).collect();
+            line(f, &cells)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(day: u32, millis: [u64; 3]) -> DayReport {
+        DayReport {
+            day,
+            parse: Duration::from_millis(millis[0]),
+            parts: vec![
+                (
+                    Part::One,
+                    PartReport {
+                        answer: "1".to_string(),
+                        elapsed: Duration::from_millis(millis[1]),
+                    },
+                ),
+                (
+                    Part::Two,
+                    PartReport {
+                        answer: "2".to_string(),
+                        elapsed: Duration::from_millis(millis[2]),
+                    },
+                ),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_report_total() {
+        assert_eq!(report(1, [1, 2, 3]).total(), Duration::from_millis(6));
+    }
+
+    #[test]
+    fn test_table_total_row() {
+        let table = Table::new(&[report(1, [1, 2, 3]), report(2, [4, 5, 6])]).to_string();
+        let total = table.lines().last().unwrap();
+
+        assert!(total.starts_with("| Total |"));
+        assert!(total.ends_with("| 5.00ms |  7.00ms |  9.00ms | 21.00ms |"));
+    }
+}
@@ -0,0 +1,162 @@
+use crate::error::ParseError;
+use std::{any::Any, collections::BTreeMap, fmt::Display, marker::PhantomData};
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Hash, Ord, PartialOrd)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "1" => Some(Part::One),
+            "2" => Some(Part::Two),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+// An answer that some valid inputs do not have, shown as "none" when it is missing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Optional<T>(pub Option<T>);
+
+impl<T: Display> Display for Optional<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.0 {
+            Some(answer) => answer.fmt(f),
+            None => write!(f, "none"),
+        }
+    }
+}
+
+// A day's puzzle: the input is parsed once and both parts are solved from the parsed model
+pub trait Solution {
+    type Input: 'static;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(contents: &str) -> Result<Self::Input, ParseError>;
+    fn part1(input: &Self::Input) -> Self::Answer1;
+    fn part2(input: &Self::Input) -> Self::Answer2;
+
+    // A human-readable breakdown of how the answers come about, for days that have one
+    fn report(_input: &Self::Input) -> Option<String> {
+        None
+    }
+
+    // Applies a command line option meant for this day to a freshly parsed input. Each day
+    // downcasts the option to the type it expects; the default ignores it.
+    fn configure(_input: &mut Self::Input, _option: &dyn Any) {}
+}
+
+// Object-safe view of a `Solution` so days with different input and answer types can share a
+// registry. The parsed input is passed around as `Any` and downcast back by the same solver.
+pub trait Solver {
+    fn parse(&self, contents: &str) -> Result<Box<dyn Any>, ParseError>;
+    fn solve(&self, part: Part, input: &dyn Any) -> String;
+    fn report(&self, input: &dyn Any) -> Option<String>;
+    fn set_option(&mut self, option: Box<dyn Any>);
+
+    fn run(&self, contents: &str, part: Part) -> Result<String, ParseError> {
+        Ok(self.solve(part, self.parse(contents)?.as_ref()))
+    }
+
+    fn run_report(&self, contents: &str) -> Result<Option<String>, ParseError> {
+        Ok(self.report(self.parse(contents)?.as_ref()))
+    }
+}
+
+struct Erased<S> {
+    day: u32,
+    option: Option<Box<dyn Any>>,
+    solution: PhantomData<S>,
+}
+
+fn downcast<S: Solution>(input: &dyn Any) -> &S::Input {
+    input
+        .downcast_ref::<S::Input>()
+        .expect("Input parsed by a different solver")
+}
+
+impl<S: Solution> Solver for Erased<S> {
+    fn parse(&self, contents: &str) -> Result<Box<dyn Any>, ParseError> {
+        match S::parse(contents) {
+            Ok(mut input) => {
+                if let Some(option) = &self.option {
+                    S::configure(&mut input, option.as_ref());
+                }
+
+                Ok(Box::new(input))
+            }
+            Err(error) => Err(error.for_day(self.day)),
+        }
+    }
+
+    fn solve(&self, part: Part, input: &dyn Any) -> String {
+        match part {
+            Part::One => S::part1(downcast::<S>(input)).to_string(),
+            Part::Two => S::part2(downcast::<S>(input)).to_string(),
+        }
+    }
+
+    fn report(&self, input: &dyn Any) -> Option<String> {
+        S::report(downcast::<S>(input))
+    }
+
+    fn set_option(&mut self, option: Box<dyn Any>) {
+        self.option = Some(option);
+    }
+}
+
+pub struct Registry {
+    solvers: BTreeMap<u32, Box<dyn Solver>>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Self {
+            solvers: BTreeMap::new(),
+        }
+    }
+
+    pub fn register<S: Solution + 'static>(&mut self, day: u32) {
+        let solver = Erased::<S> {
+            day,
+            option: None,
+            solution: PhantomData,
+        };
+
+        self.solvers.insert(day, Box::new(solver));
+    }
+
+    // Hands `option` to `day`'s solver, which applies it to every input it parses from now on
+    pub fn configure(&mut self, day: u32, option: Box<dyn Any>) {
+        if let Some(solver) = self.solvers.get_mut(&day) {
+            solver.set_option(option);
+        }
+    }
+
+    pub fn days(&self) -> impl Iterator<Item = u32> + '_ {
+        self.solvers.keys().copied()
+    }
+
+    pub fn get(&self, day: u32) -> Option<&dyn Solver> {
+        self.solvers.get(&day).map(|solver| solver.as_ref())
+    }
+}
+
+impl Default for Registry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,56 @@
+use advent_of_code_2023::{
+    day10::{self, Day10},
+    day5::{self, Day5},
+    day7::{Hand, HandType, RuleSet},
+    grid::Grid,
+    solution::Solution,
+};
+
+const ALMANAC: &str = "seeds: 79 14 55 13\n\nseed-to-soil map:\n50 98 2\n52 50 48\n\nsoil-to-fertilizer map:\n0 15 37\n37 52 2\n39 0 15\n\nfertilizer-to-water map:\n49 53 8\n0 11 42\n42 0 7\n57 7 4\n\nwater-to-light map:\n88 18 7\n18 25 70\n\nlight-to-temperature map:\n45 77 23\n81 45 19\n68 64 13\n\ntemperature-to-humidity map:\n0 69 1\n1 0 69\n\nhumidity-to-location map:\n60 56 37\n56 93 4";
+
+#[test]
+fn test_day5_model() {
+    let almanac = Day5::parse(ALMANAC).unwrap();
+
+    assert_eq!(almanac.seeds, vec![79, 14, 55, 13]);
+    assert_eq!(almanac.sections.len(), 7);
+    assert_eq!(day5::find_dest(&almanac.sections[0], 79), 81);
+    assert_eq!(day5::find_source(&almanac.sections[0], 81), 79);
+
+    let location = almanac
+        .sections
+        .iter()
+        .fold(13, |value, section| day5::find_dest(section, value));
+    assert_eq!(location, 35);
+    assert_eq!(day5::part1::seed_locations(&almanac), Some(35));
+}
+
+#[test]
+fn test_day7_model() {
+    let hand = Hand::parse("KTJJT 220", &RuleSet::standard()).unwrap();
+
+    assert_eq!(hand.bid, 220);
+    assert_eq!(hand.hand_type, HandType::TwoPair);
+    assert_eq!(
+        Hand::parse("KTJJT 220", &RuleSet::jokers())
+            .unwrap()
+            .hand_type,
+        HandType::FourOfAKind
+    );
+}
+
+#[test]
+fn test_day10_model() {
+    let map = Day10::parse("..F7.\n.FJ|.\nSJ.L7\n|F--J\nLJ...").unwrap();
+
+    assert_eq!(day10::part1::steps_to_furthest_tile(&map), 8);
+    assert_eq!(map.get_loop_vertices().len(), 16);
+}
+
+#[test]
+fn test_grid() {
+    let grid = Grid::parse("123\n456", |c| c.to_digit(10)).unwrap();
+
+    assert_eq!(grid[(1, 2)], 6);
+    assert_eq!(grid.transpose().to_string(), "14\n25\n36\n");
+}
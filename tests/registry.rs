@@ -0,0 +1,92 @@
+use advent_of_code_2023::{day2::Bag, registry, runner::run_day, solution::Part};
+
+#[test]
+fn test_every_day_is_registered() {
+    assert_eq!(
+        registry().days().collect::<Vec<_>>(),
+        (1..=25).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_run_through_registry() {
+    let registry = registry();
+    let solver = registry.get(1).unwrap();
+
+    assert_eq!(
+        solver.run("1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet", Part::One),
+        Ok("142".to_string())
+    );
+    assert_eq!(
+        registry.get(25).unwrap().run("", Part::Two),
+        Ok("Merry Christmas!".to_string())
+    );
+}
+
+#[test]
+fn test_configured_bag() {
+    let games = "Game 1: 3 blue, 4 red\nGame 2: 13 red\nGame 3: 5 red";
+    let mut small = registry();
+    small.configure(2, Box::new(Bag::parse("red=4, blue=3").unwrap()));
+
+    assert_eq!(
+        registry().get(2).unwrap().run(games, Part::One),
+        Ok("4".to_string())
+    );
+    assert_eq!(
+        small.get(2).unwrap().run(games, Part::One),
+        Ok("1".to_string())
+    );
+}
+
+#[test]
+fn test_run_day_reports_both_parts() {
+    let registry = registry();
+    let report = run_day(
+        9,
+        registry.get(9).unwrap(),
+        "0 3 6 9 12 15\n1 3 6 10 15 21\n10 13 16 21 30 45",
+        &[Part::One, Part::Two],
+    )
+    .unwrap();
+
+    let answers: Vec<(Part, &str)> = report
+        .parts
+        .iter()
+        .map(|(part, result)| (*part, result.answer.as_str()))
+        .collect();
+
+    assert_eq!(answers, vec![(Part::One, "114"), (Part::Two, "2")]);
+}
+
+#[test]
+fn test_parse_errors_are_located() {
+    let error = registry()
+        .get(9)
+        .unwrap()
+        .run("0 3 6\n1 three 6", Part::One)
+        .unwrap_err();
+
+    assert_eq!((error.day, error.line, error.column), (9, 2, 3));
+    assert_eq!(
+        error.to_string(),
+        "Day 9, line 2, column 3: expected a number, found `three`"
+    );
+}
+
+#[test]
+fn test_reports() {
+    let registry = registry();
+    let report = registry
+        .get(4)
+        .unwrap()
+        .run_report("Card 1: 41 48 | 41 86\nCard 2: 13 32 | 61 30")
+        .unwrap();
+
+    assert_eq!(report.as_deref(), Some("Card 1: 1\nCard 2: 2\nTotal: 3\n"));
+    assert_eq!(
+        registry.get(9).unwrap().run_report("1 2 3"),
+        Ok(Some("Line 1: degree 1, previous 0, next 4\n".to_string()))
+    );
+    assert_eq!(registry.get(1).unwrap().run_report("1abc2"), Ok(None));
+}